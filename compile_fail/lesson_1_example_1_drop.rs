@@ -0,0 +1,9 @@
+// lesson 1 example 1: we can not drop the owned value while references to it are still used
+fn main() {
+    let data = String::from("Hello");
+    let reference1 = &data;
+    let reference2 = &data;
+    drop(data); // this will cause an error
+    println!("reference1: {}", reference1);
+    println!("reference2: {}", reference2);
+}
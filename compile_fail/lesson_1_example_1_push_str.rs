@@ -0,0 +1,9 @@
+// lesson 1 example 1: data was never declared mut so it can not be changed
+fn main() {
+    let data = String::from("Hello");
+    let reference1 = &data;
+    let reference2 = &data;
+    data.push_str(" World"); // this will cause an error
+    println!("reference1: {}", reference1);
+    println!("reference2: {}", reference2);
+}
@@ -0,0 +1,11 @@
+// lesson 1 example 2: no shared reference while a mutable reference is still used
+fn main() {
+    let mut data = String::from("Hello");
+
+    let reference1 = &mut data;
+    let reference2 = &data; // this will cause an error
+    reference1.push_str(" World");
+
+    println!("reference1: {}", reference1);
+    println!("reference2: {}", reference2);
+}
@@ -0,0 +1,10 @@
+// lesson 1 example 4: we can NOT pass in an owned object and return &my_text
+fn cant_make_ref(my_text: String) -> &String {
+    &my_text
+}
+
+fn main() {
+    let s: String = String::from("message");
+    let r = cant_make_ref(s);
+    println!("{:?}", r);
+}
@@ -0,0 +1,23 @@
+// lesson 2 example 3.5: consume_return_b hands back 'b which ends with b_string
+#[derive(Debug)]
+struct SplitStruct<'a,'b> {
+    a: &'a String,
+    b: &'b String
+}
+
+impl <'a,'b> SplitStruct<'a,'b> {
+    fn consume_return_b(self) -> &'b String {
+        self.b
+    }
+}
+
+fn main() {
+    let a_string = String::from("aaa");
+    let a_ref;
+    {
+        let b_string = String::from("bbb");
+        let data = SplitStruct {a: &a_string, b: &b_string};
+        a_ref = data.consume_return_b(); //this can not work. what was inferred?
+    }
+    println!("{:?}", a_ref);
+}
@@ -0,0 +1,23 @@
+// lesson 2 example 3.5: process_a borrows data itself, not the 'a it holds
+#[derive(Debug)]
+struct SplitStruct<'a,'b> {
+    a: &'a String,
+    b: &'b String
+}
+
+impl <'a,'b> SplitStruct<'a,'b> {
+    fn process_a(self: &Self) -> &String {
+        self.a
+    }
+}
+
+fn main() {
+    let a_string = String::from("aaa");
+    let a_ref;
+    {
+        let b_string = String::from("bbb");
+        let data = SplitStruct {a: &a_string, b: &b_string};
+        a_ref = data.process_a(); //this is not allowed, it was not moved out of data
+    }
+    println!("{:?}", a_ref);
+}
@@ -0,0 +1,8 @@
+// lesson 3 example 5: naming the lifetime does not let us return a ref to a local
+fn attempted_return_owned<'a>(text: String) -> &'a String {
+    &text //we had to be clear about its lifetime but could still not return it
+}
+
+fn main() {
+    println!("{}", attempted_return_owned(String::from("Hello")));
+}
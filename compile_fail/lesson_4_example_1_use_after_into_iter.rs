@@ -0,0 +1,8 @@
+// lesson 4 example 1: into_iter() moves names into the loop
+fn main() {
+    let names = vec![String::from("ann"), String::from("bob")];
+    for name in names.into_iter() {
+        println!("owned: {}", name);
+    }
+    println!("{:?}", names);
+}
@@ -0,0 +1,10 @@
+// lesson 4 example 2: the returned iterator captures the borrow of numbers
+fn evens(numbers: &[i32]) -> impl Iterator<Item = i32> {
+    numbers.iter().copied().filter(|n| n % 2 == 0)
+}
+
+fn main() {
+    let numbers = [1, 2, 3, 4, 5, 6];
+    let even: Vec<i32> = evens(&numbers).collect();
+    println!("{:?}", even);
+}
@@ -0,0 +1,9 @@
+// lesson 4 example 4: two iter_mut calls hand out two &mut at once
+fn main() {
+    let mut numbers = vec![1, 2, 3];
+    let mut each = numbers.iter_mut();
+    let first = each.next().unwrap();
+    let second = numbers.iter_mut().nth(1).unwrap();
+    *first += *second;
+    println!("{:?}", numbers);
+}
@@ -0,0 +1,15 @@
+// lesson 5 example 1: the Box<dyn Display> field is Box<dyn Display + 'static>
+use std::fmt::Display;
+
+struct Shelf {
+    items: Vec<Box<dyn Display>>,
+}
+
+fn main() {
+    let name = String::from("Ferris");
+    let mut shelf = Shelf { items: Vec::new() };
+    shelf.items.push(Box::new(&name));
+    for item in &shelf.items {
+        println!("{}", item);
+    }
+}
@@ -0,0 +1,11 @@
+// lesson 5 example 4: the boxed closure borrows skip but the field wants 'static
+struct StaticPipeline {
+    step: Box<dyn Fn(&str) -> &str>,
+}
+
+fn main() {
+    let skip = 2;
+    let skip_ref = &skip;
+    let pipeline = StaticPipeline { step: Box::new(move |s| &s[*skip_ref..]) };
+    println!("{}", (pipeline.step)("> quoted reply"));
+}
@@ -0,0 +1,9 @@
+// lesson 6 example 1: covariance only shrinks, a short borrow can not become 'static
+fn main() {
+    let mut long: &'static str = "static";
+    {
+        let local = String::from("local");
+        long = &local;
+    }
+    println!("{}", long);
+}
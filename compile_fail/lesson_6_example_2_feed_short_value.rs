@@ -0,0 +1,13 @@
+// lesson 6 example 2: &mut T is invariant so T stays &'static str
+fn feed<T>(input: &mut T, value: T) {
+    *input = value;
+}
+
+fn main() {
+    let mut pet: &'static str = "meow";
+    {
+        let spike = String::from("bark");
+        feed(&mut pet, spike.as_str());
+    }
+    println!("{}", pet);
+}
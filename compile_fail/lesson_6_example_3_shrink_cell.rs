@@ -0,0 +1,11 @@
+// lesson 6 example 3: Cell<&'a T> is invariant so &Cell<&'static str> can not shrink
+use std::cell::Cell;
+
+fn main() {
+    let cell: Cell<&'static str> = Cell::new("static");
+    {
+        let local = String::from("local");
+        let short: &Cell<&str> = &cell; short.set(&local);
+    }
+    println!("{}", cell.get());
+}
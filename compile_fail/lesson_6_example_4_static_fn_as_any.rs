@@ -0,0 +1,10 @@
+// lesson 6 example 4: fn(&'a T) is contravariant, a fn(&'static str) can not accept any &str
+fn print_static(s: &'static str) {
+    println!("static: {}", s);
+}
+
+fn main() {
+    let handler: fn(&str) = print_static;
+    let local = String::from("local");
+    handler(&local);
+}
@@ -0,0 +1,29 @@
+// lesson 6 example 5: PhantomData<&'a T> keeps data borrowed while the view is used
+use std::marker::PhantomData;
+
+struct View<'a, T> {
+    ptr: *const T,
+    len: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> View<'a, T> {
+    fn new(items: &'a [T]) -> View<'a, T> {
+        View { ptr: items.as_ptr(), len: items.len(), _marker: PhantomData }
+    }
+
+    fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.len {
+            unsafe { Some(&*self.ptr.add(index)) }
+        } else {
+            None
+        }
+    }
+}
+
+fn main() {
+    let data = vec![10, 20, 30];
+    let view = View::new(&data);
+    drop(data);
+    println!("{:?}", view.get(1));
+}
@@ -0,0 +1,8 @@
+// lesson 7 example 1: greet holds &name until its last call
+fn main() {
+    let mut name = String::from("Ferris");
+    let greet = || println!("hello {}", name);
+    greet();
+    name.push_str(" the crab");
+    greet();
+}
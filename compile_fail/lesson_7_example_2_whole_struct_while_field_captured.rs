@@ -0,0 +1,19 @@
+// lesson 7 example 2: describe needs all of player while bump holds &mut player.score
+struct Player {
+    name: String,
+    score: u32,
+}
+
+impl Player {
+    fn describe(&self) -> String {
+        format!("{} has {} points", self.name, self.score)
+    }
+}
+
+fn main() {
+    let mut player = Player { name: String::from("ann"), score: 0 };
+    let mut bump = || player.score += 1;
+    bump();
+    println!("{}", player.describe());
+    bump();
+}
@@ -0,0 +1,9 @@
+// lesson 7 example 3: the returned closure borrows text, which make_counter drops
+fn make_counter() -> impl Fn() -> usize {
+    let text = String::from("counted");
+    || text.len()
+}
+
+fn main() {
+    println!("{}", make_counter()());
+}
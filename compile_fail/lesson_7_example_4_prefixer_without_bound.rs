@@ -0,0 +1,10 @@
+// lesson 7 example 4: the returned closure holds prefix but the type does not say so
+fn prefixer(prefix: &str) -> impl Fn(&str) -> String {
+    move |s| format!("{}{}", prefix, s)
+}
+
+fn main() {
+    let marker = String::from("> ");
+    let quote = prefixer(&marker);
+    println!("{}", quote("borrowed"));
+}
@@ -0,0 +1,8 @@
+// lesson 7 example 5: tally holds &mut count until its last call
+fn main() {
+    let mut count = 0;
+    let mut tally = || count += 1;
+    tally();
+    println!("{}", count);
+    tally();
+}
@@ -0,0 +1,8 @@
+// lesson 8 example 1: the spawned closure borrows scores, which may be gone before the thread ends
+use std::thread;
+
+fn main() {
+    let scores = vec![3, 1, 4];
+    let handle = thread::spawn(|| scores.iter().sum::<i32>());
+    println!("sum from the thread: {}", handle.join().unwrap());
+}
@@ -0,0 +1,9 @@
+// lesson 8 example 2: moving view into the thread still leaves it borrowing scores
+use std::thread;
+
+fn main() {
+    let scores = vec![3, 1, 4];
+    let view = &scores;
+    let handle = thread::spawn(move || view.len());
+    println!("{}", handle.join().unwrap());
+}
@@ -0,0 +1,18 @@
+// lesson 8 example 3: the writer threads hold &mut chunks of doubled until the scope ends
+use std::thread;
+
+fn main() {
+    let scores = vec![3, 1, 4, 1, 5, 9];
+    let mut doubled = vec![0; scores.len()];
+    thread::scope(|s| {
+        for (chunk, out) in scores.chunks(3).zip(doubled.chunks_mut(3)) {
+            s.spawn(move || {
+                for (score, slot) in chunk.iter().zip(out) {
+                    *slot = score * 2;
+                }
+            });
+        }
+        s.spawn(|| doubled.clear());
+    });
+    println!("{:?}", doubled);
+}
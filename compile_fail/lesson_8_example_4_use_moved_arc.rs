@@ -0,0 +1,11 @@
+// lesson 8 example 4: first was moved into the thread, Arc or not
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let scores = Arc::new(vec![3, 1, 4]);
+    let first = Arc::clone(&scores);
+    let handle = thread::spawn(move || first.len());
+    println!("{:?}", first);
+    println!("the thread counted {}", handle.join().unwrap());
+}
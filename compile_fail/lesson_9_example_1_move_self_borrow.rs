@@ -0,0 +1,13 @@
+// lesson 9 example 1: holding borrows itself, so it can not be moved
+struct HoldingStruct<'a> {
+    text: String,
+    word: &'a str,
+}
+
+fn main() {
+    let mut holding = HoldingStruct { text: String::from("Hello world"), word: "" };
+    holding.word = holding.text.split(' ').next().unwrap();
+    println!("{} from {}", holding.word, holding.text);
+    let moved = holding;
+    println!("{}", moved.word);
+}
@@ -0,0 +1,15 @@
+// lesson 9 example 2: hold would return a borrow of its own parameter
+struct HoldingStruct<'a> {
+    text: String,
+    word: &'a str,
+}
+
+fn hold<'a>(text: String) -> HoldingStruct<'a> {
+    let word = text.split(' ').next().unwrap();
+    HoldingStruct { text, word }
+}
+
+fn main() {
+    let holding = hold(String::from("Hello world"));
+    println!("{} from {}", holding.word, holding.text);
+}
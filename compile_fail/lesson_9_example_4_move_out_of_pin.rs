@@ -0,0 +1,19 @@
+// lesson 9 example 4: PhantomPinned means the struct can not be moved out of its Pin
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+struct PinnedHolding {
+    text: String,
+    text_ref: *const String,
+    _pin: PhantomPinned,
+}
+
+fn main() {
+    let moved: Pin<Box<PinnedHolding>> = Box::pin(PinnedHolding {
+        text: String::from("Hello world"),
+        text_ref: std::ptr::null(),
+        _pin: PhantomPinned,
+    });
+    let unpinned = *moved;
+    println!("{} {:?}", unpinned.text, unpinned.text_ref);
+}
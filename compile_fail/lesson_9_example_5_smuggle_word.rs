@@ -0,0 +1,24 @@
+// lesson 9 example 5: for<'this> means no word may outlive the call to with_words
+struct OwnedWords {
+    words: Vec<&'static str>,
+    text: String,
+}
+
+impl OwnedWords {
+    fn new(text: String) -> OwnedWords {
+        let borrowed: &'static str = unsafe { &*(text.as_str() as *const str) };
+        OwnedWords { words: borrowed.split_whitespace().collect(), text }
+    }
+
+    fn with_words<R>(&self, f: impl for<'this> FnOnce(&'this [&'this str]) -> R) -> R {
+        f(&self.words)
+    }
+}
+
+fn main() {
+    let moved = OwnedWords::new(String::from("self referential structs are hard"));
+    let mut found: Vec<&str> = Vec::new();
+    moved.with_words(|words| found.push(words[0]));
+    drop(moved);
+    println!("{:?}", found);
+}
@@ -0,0 +1,9 @@
+// lesson 1 example 1: the drop comes after the last use of both references
+fn main() {
+    let data = String::from("Hello");
+    let reference1 = &data;
+    let reference2 = &data;
+    println!("reference1: {}", reference1);
+    println!("reference2: {}", reference2);
+    drop(data);
+}
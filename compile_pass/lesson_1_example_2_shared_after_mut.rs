@@ -0,0 +1,10 @@
+// lesson 1 example 2: the shared reference is taken after the mutable one is last used
+fn main() {
+    let mut data = String::from("Hello");
+
+    let reference1 = &mut data;
+    reference1.push_str(" World");
+    let reference2 = &data;
+
+    println!("reference2: {}", reference2);
+}
@@ -0,0 +1,21 @@
+// lesson 2 example 2: b is dropped once s is no longer used
+#[derive(Debug)]
+struct UnifiedStruct<'a> {
+    a: &'a String,
+    b: &'a String
+}
+
+fn main() {
+    let a: String = String::from("a");
+    let a_ref = &a;
+    {
+        let b: String = String::from("b");
+        {
+            let s = UnifiedStruct { a: a_ref, b: &b };
+            println!("{:?}", &s);
+            println!("{:?}", s.a);
+            drop(b);
+        }
+    }
+    println!("{:?}", a_ref);
+}
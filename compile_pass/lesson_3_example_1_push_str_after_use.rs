@@ -0,0 +1,11 @@
+// lesson 3 example 1: s may change once the returned &str is no longer used
+fn get_length_with_lifetime<'a>(s: &'a str) -> (usize, &'a str) {
+    (s.len(), s)
+}
+
+fn main() {
+    let mut s = String::from("Hello, world!");
+    let (length, s_ref) = get_length_with_lifetime(&s);
+    println!("Length with lifetime: {} {}", length, s_ref);
+    s.push_str(" New text");
+}
@@ -0,0 +1,10 @@
+// lesson 5 example 4: the closure owns its copy of skip, so it is 'static
+struct StaticPipeline {
+    step: Box<dyn Fn(&str) -> &str>,
+}
+
+fn main() {
+    let skip = 2;
+    let pipeline = StaticPipeline { step: Box::new(move |s| &s[skip..]) };
+    println!("{}", (pipeline.step)("> quoted reply"));
+}
@@ -0,0 +1,14 @@
+// lesson 6 example 2: a 'static value is exactly the type the variable holds
+fn feed<T>(input: &mut T, value: T) {
+    *input = value;
+}
+
+fn main() {
+    let mut pet: &'static str = "meow";
+    {
+        let spike = String::from("bark");
+        println!("{} and {}", pet, spike);
+    }
+    feed(&mut pet, "purr");
+    println!("{}", pet);
+}
@@ -0,0 +1,14 @@
+// lesson 7 example 2: bump captures only player.score, player.name is free to read
+struct Player {
+    name: String,
+    score: u32,
+}
+
+fn main() {
+    let mut player = Player { name: String::from("ann"), score: 0 };
+    let mut bump = || player.score += 1;
+    bump();
+    println!("{} is playing", player.name);
+    bump();
+    println!("{}", player.score);
+}
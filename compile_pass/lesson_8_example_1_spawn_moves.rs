@@ -0,0 +1,8 @@
+// lesson 8 example 1: move hands scores to the thread so the closure is 'static
+use std::thread;
+
+fn main() {
+    let scores = vec![3, 1, 4];
+    let handle = thread::spawn(move || scores.iter().sum::<i32>());
+    println!("sum from the thread: {}", handle.join().unwrap());
+}
@@ -0,0 +1,12 @@
+// lesson 8 example 3: scoped threads may share &scores
+use std::thread;
+
+fn main() {
+    let scores = vec![3, 1, 4, 1, 5, 9];
+    thread::scope(|s| {
+        let total = s.spawn(|| scores.iter().sum::<i32>());
+        let largest = s.spawn(|| scores.iter().max().copied());
+        println!("total {} largest {:?}", total.join().unwrap(), largest.join().unwrap());
+    });
+    println!("{:?}", scores);
+}
@@ -0,0 +1,12 @@
+// lesson 9 example 1: moved before word borrows text, so nothing borrows holding yet
+struct HoldingStruct<'a> {
+    text: String,
+    word: &'a str,
+}
+
+fn main() {
+    let holding = HoldingStruct { text: String::from("Hello world"), word: "" };
+    let mut moved = holding;
+    moved.word = moved.text.split(' ').next().unwrap();
+    println!("{} from {}", moved.word, moved.text);
+}
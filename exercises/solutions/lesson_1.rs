@@ -0,0 +1,8 @@
+pub fn first_word(text: &str) -> &str {
+    text.split(' ').next().unwrap_or(text)
+}
+
+pub fn append_world(data: &mut String) -> &String {
+    data.push_str(" World");
+    data
+}
@@ -0,0 +1,24 @@
+#[derive(Debug)]
+pub struct UnifiedStruct<'a> {
+    pub a: &'a String,
+    pub b: &'a String,
+}
+
+impl<'a> UnifiedStruct<'a> {
+    pub fn update_b(&mut self, b: &'a String) -> &String {
+        self.b = b;
+        b
+    }
+}
+
+#[derive(Debug)]
+pub struct SplitStruct<'a, 'b> {
+    pub a: &'a String,
+    pub b: &'b String,
+}
+
+impl<'a, 'b> SplitStruct<'a, 'b> {
+    pub fn consume_return_b(self) -> &'b String {
+        self.b
+    }
+}
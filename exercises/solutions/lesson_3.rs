@@ -0,0 +1,23 @@
+pub fn shortest_length<'a, 'b: 'a>(x: &'a String, y: &'b String, z: &'b String) -> &'a String {
+    let mut shortest = x;
+    for candidate in [y, z] {
+        if candidate.len() < shortest.len() {
+            shortest = candidate;
+        }
+    }
+    shortest
+}
+
+pub struct HoldingStruct {
+    pub text: String,
+}
+
+pub fn hold_and_ref<'a>(holding: &'a mut HoldingStruct, text: String) -> &'a String {
+    holding.text = text;
+    &holding.text
+}
+
+pub fn apply_to_str<F>(text: &str, goober: &str, f: F) -> String
+    where F: for<'a, 'goober> Fn(&'a str, &'goober str) -> &'a str {
+    f(text, goober).to_string()
+}
@@ -0,0 +1,17 @@
+// Lesson 1 exercises: Reference Review - Borrowing and scope
+//
+// Replace each todo!() and run `learn-lifetimes check 1` to grade your work.
+// The signatures are already correct, read them closely, the elided lifetimes
+// tell you what the body may return.
+
+/// The text before the first space, or all of it when there is no space.
+/// There is only one reference in, so the returned &str is elided to borrow from text.
+pub fn first_word(text: &str) -> &str {
+    todo!()
+}
+
+/// Push " World" onto data and hand back a shared reference to the result.
+/// The mutable borrow we were given is downgraded to the shared one we return.
+pub fn append_world(data: &mut String) -> &String {
+    todo!()
+}
@@ -0,0 +1,31 @@
+// Lesson 2 exercises: Struct and self
+//
+// Replace each todo!() and run `learn-lifetimes check 2` to grade your work.
+
+/// Both references share one lifetime, just like lesson 2 example 2.
+#[derive(Debug)]
+pub struct UnifiedStruct<'a> {
+    pub a: &'a String,
+    pub b: &'a String,
+}
+
+impl<'a> UnifiedStruct<'a> {
+    /// Store the new b and return it. It must be 'a because we keep it in self.
+    pub fn update_b(&mut self, b: &'a String) -> &String {
+        todo!()
+    }
+}
+
+/// Each reference keeps its own lifetime, just like lesson 2 example 3.
+#[derive(Debug)]
+pub struct SplitStruct<'a, 'b> {
+    pub a: &'a String,
+    pub b: &'b String,
+}
+
+impl<'a, 'b> SplitStruct<'a, 'b> {
+    /// Consume the struct and return b, which may outlive whatever a points to.
+    pub fn consume_return_b(self) -> &'b String {
+        todo!()
+    }
+}
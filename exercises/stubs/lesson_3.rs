@@ -0,0 +1,26 @@
+// Lesson 3 exercises: Complex lifetime applications
+//
+// Replace each todo!() and run `learn-lifetimes check 3` to grade your work.
+
+/// Return the shortest of the three strings, the first one on a tie.
+/// 'b: 'a reads "'b outlives 'a", so y and z may be used wherever x is, the
+/// lifetime subtyping alternative from lesson 3 example 2.
+pub fn shortest_length<'a, 'b: 'a>(x: &'a String, y: &'b String, z: &'b String) -> &'a String {
+    todo!()
+}
+
+pub struct HoldingStruct {
+    pub text: String,
+}
+
+/// Move text into holding and return a reference to it, as in lesson 3 example 5.
+pub fn hold_and_ref<'a>(holding: &'a mut HoldingStruct, text: String) -> &'a String {
+    todo!()
+}
+
+/// Call f with text and goober and return what it hands back as an owned String.
+/// The for<'a, 'goober> bound lets f work with whatever lifetimes we call it with.
+pub fn apply_to_str<F>(text: &str, goober: &str, f: F) -> String
+    where F: for<'a, 'goober> Fn(&'a str, &'goober str) -> &'a str {
+    todo!()
+}
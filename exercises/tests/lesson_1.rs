@@ -0,0 +1,16 @@
+#[test]
+fn first_word() {
+    assert_eq!("Hello", super::first_word("Hello World"));
+    assert_eq!("Hello", super::first_word("Hello"));
+    let text = String::from("borrowed text");
+    let word = super::first_word(&text);
+    assert_eq!(text.as_ptr(), word.as_ptr(), "return a slice of text, not a copy");
+}
+
+#[test]
+fn append_world() {
+    let mut data = String::from("Hello");
+    let shared = super::append_world(&mut data);
+    assert_eq!("Hello World", shared);
+    assert_eq!("Hello World", data);
+}
@@ -0,0 +1,22 @@
+#[test]
+fn update_b() {
+    let a = String::from("a");
+    let b = String::from("b");
+    let new_b = String::from("new b");
+    let mut unified = super::UnifiedStruct { a: &a, b: &b };
+    assert_eq!("new b", unified.update_b(&new_b));
+    assert_eq!("new b", unified.b);
+    assert_eq!("a", unified.a);
+}
+
+#[test]
+fn consume_return_b() {
+    let b_string = String::from("bbb");
+    let b_ref;
+    {
+        let a_string = String::from("aaa");
+        let data = super::SplitStruct { a: &a_string, b: &b_string };
+        b_ref = data.consume_return_b();
+    }
+    assert_eq!("bbb", b_ref);
+}
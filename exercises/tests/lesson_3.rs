@@ -0,0 +1,25 @@
+#[test]
+fn shortest_length() {
+    let x = String::from("Hello");
+    let y = String::from("World");
+    let z = String::from("!");
+    assert_eq!("!", super::shortest_length(&x, &y, &z));
+    assert_eq!("Hello", super::shortest_length(&x, &y, &x));
+    let short = String::from("a");
+    assert_eq!("a", super::shortest_length(&x, &short, &y));
+}
+
+#[test]
+fn hold_and_ref() {
+    let mut holding = super::HoldingStruct { text: String::from("Hello") };
+    assert_eq!("world", super::hold_and_ref(&mut holding, String::from("world")));
+    assert_eq!("world", holding.text);
+}
+
+#[test]
+fn apply_to_str() {
+    let result = super::apply_to_str("Hello, world!", "goober", |text, _goober| &text[0..4]);
+    assert_eq!("Hell", result);
+    let result = super::apply_to_str("Hello", "goober", |_text, _goober| "static");
+    assert_eq!("static", result);
+}
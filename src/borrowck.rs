@@ -0,0 +1,635 @@
+// A borrow checker for a toy language, small enough to read in one sitting.
+// It follows the rules from lesson 1 with non-lexical lifetimes: a borrow is
+// live from where it is taken until the last use of the reference holding it,
+// not until the end of the block.
+//
+//     let x = new;        a new owned value
+//     let mut x = new;    one that may be mutated or mutably borrowed
+//     let r = &x;         a shared borrow
+//     let m = &mut x;     a mutable borrow
+//     let y = x;          moves x, only a shared reference is Copy, a &mut is moved too
+//     let r;  r = &x;     declare now, assign later, only a `let mut` may be assigned again
+//     use r;              read a value or a reference
+//     write x;            mutate through x, like x.push_str(..)
+//     drop x;             move x into drop
+//     { ... }             a block, owned values die at its closing brace
+//
+// Statements end with ; and // starts a comment.
+
+pub(crate) const SAMPLES: &[(&str, &str)] = &[
+    ("shared", "\
+// lesson 1 example 1, many shared references are fine
+let data = new;
+let reference1 = &data;
+let reference2 = &data;
+use reference1;
+use reference2;
+drop data;"),
+    ("drop-while-borrowed", "\
+// lesson 1 example 1 with the drop moved up, error[E0505]
+let data = new;
+let reference1 = &data;
+drop data;
+use reference1;"),
+    ("shared-while-mutable", "\
+// lesson 1 example 2, error[E0502]
+let mut data = new;
+let reference1 = &mut data;
+let reference2 = &data;
+write reference1;
+use reference2;"),
+    ("nll", "\
+// lesson 1 example 2 as written, the mutable borrow ends at its last use
+let mut data = new;
+let reference1 = &mut data;
+write reference1;
+use reference1;
+use data;"),
+    ("does-not-live-long-enough", "\
+// lesson 2 example 3.5, error[E0597]
+let a_ref;
+{
+    let b_string = new;
+    a_ref = &b_string;
+}
+use a_ref;"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    New,
+    Borrow { target: String, mutable: bool },
+    Name(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stmt {
+    Let { name: String, mutable: bool, init: Option<Expr> },
+    Assign { name: String, value: Expr },
+    Use(String),
+    Write(String),
+    Drop(String),
+    Open,
+    Close,
+}
+
+/// A statement with the source line it came from
+struct Line {
+    stmt: Stmt,
+    number: usize,
+}
+
+fn tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
+        }
+        if !c.is_whitespace() {
+            tokens.push(c.to_string());
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+fn parse_expr(tokens: &[String]) -> Result<Expr, String> {
+    let words: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    match words.as_slice() {
+        ["new"] => Ok(Expr::New),
+        ["&", "mut", target] => Ok(Expr::Borrow { target: target.to_string(), mutable: true }),
+        ["&", target] => Ok(Expr::Borrow { target: target.to_string(), mutable: false }),
+        [name] if is_name(name) => Ok(Expr::Name(name.to_string())),
+        _ => Err(format!("expected new, &x, &mut x or a name but found `{}`", words.join(" "))),
+    }
+}
+
+fn is_name(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && !["let", "mut", "new", "use", "write", "drop"].contains(&word)
+}
+
+fn parse(program: &str) -> Result<Vec<Line>, String> {
+    let mut lines = Vec::new();
+    let mut depth = 0;
+    for (index, text) in program.lines().enumerate() {
+        let number = index + 1;
+        let code = text.split("//").next().unwrap_or("");
+        let mut current: Vec<String> = Vec::new();
+        for token in tokens(code) {
+            match token.as_str() {
+                "{" if current.is_empty() => {
+                    depth += 1;
+                    lines.push(Line { stmt: Stmt::Open, number });
+                }
+                "}" if current.is_empty() && depth > 0 => {
+                    depth -= 1;
+                    lines.push(Line { stmt: Stmt::Close, number });
+                }
+                "}" if current.is_empty() => return Err(format!("line {}: unexpected closing delimiter `}}`", number)),
+                ";" => {
+                    let stmt = parse_stmt(&current).map_err(|e| format!("line {}: {}", number, e))?;
+                    lines.push(Line { stmt, number });
+                    current.clear();
+                }
+                _ => current.push(token),
+            }
+        }
+        if !current.is_empty() {
+            return Err(format!("line {}: expected ; after `{}`", number, current.join(" ")));
+        }
+    }
+    if depth > 0 {
+        return Err("unclosed delimiter `{`".to_string());
+    }
+    Ok(lines)
+}
+
+fn parse_stmt(tokens: &[String]) -> Result<Stmt, String> {
+    let words: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    let name = |word: &str| if is_name(word) { Ok(word.to_string()) } else { Err(format!("`{}` is not a name", word)) };
+    match words.as_slice() {
+        ["let", "mut", rest @ ..] => parse_let(rest, true, tokens),
+        ["let", rest @ ..] => parse_let(rest, false, tokens),
+        ["use", target] => Ok(Stmt::Use(name(target)?)),
+        ["write", target] => Ok(Stmt::Write(name(target)?)),
+        ["drop", target] => Ok(Stmt::Drop(name(target)?)),
+        [target, "=", ..] => Ok(Stmt::Assign { name: name(target)?, value: parse_expr(&tokens[2..])? }),
+        _ => Err(format!("unknown statement `{}`", words.join(" "))),
+    }
+}
+
+fn parse_let(rest: &[&str], mutable: bool, tokens: &[String]) -> Result<Stmt, String> {
+    match rest {
+        [name] if is_name(name) => Ok(Stmt::Let { name: name.to_string(), mutable, init: None }),
+        [name, "=", ..] if is_name(name) => {
+            let skip = tokens.len() - rest.len() + 2;
+            Ok(Stmt::Let { name: name.to_string(), mutable, init: Some(parse_expr(&tokens[skip..])?) })
+        }
+        _ => Err(format!("expected `let name = ...` but found `let {}`", rest.join(" "))),
+    }
+}
+
+/// A diagnostic in the shape rustc prints them
+#[derive(Debug)]
+pub(crate) struct Violation {
+    pub(crate) code: &'static str,
+    pub(crate) message: String,
+    pub(crate) line: usize,
+    /// (line, note) pairs pointing at the borrow and its later use
+    pub(crate) notes: Vec<(usize, String)>,
+}
+
+struct Binding {
+    name: String,
+    mutable: bool,
+    /// is this binding a reference, and to which loans. The first is the loan it
+    /// took itself, any after it are the loans of the reference it borrowed.
+    loans: Vec<usize>,
+    initialized: bool,
+    moved: Option<usize>,
+    /// statement indexes where this binding is read
+    uses: Vec<usize>,
+    /// statement indexes where it is assigned, a reassignment ends the loans it held
+    assigns: Vec<usize>,
+    /// statement index of its let
+    declared: usize,
+}
+
+struct Loan {
+    owner: usize,
+    mutable: bool,
+    created: usize,
+    /// bindings holding the reference, and the statement from which they hold it
+    holders: Vec<(usize, usize)>,
+}
+
+/// Resolves every name to the binding it refers to, before checking, so
+/// we know the last use of every reference ahead of time as NLL needs.
+fn resolve(lines: &[Line]) -> (Vec<Binding>, Vec<Vec<Option<usize>>>, Vec<Violation>) {
+    let mut bindings: Vec<Binding> = Vec::new();
+    let mut scopes: Vec<Vec<usize>> = vec![Vec::new()];
+    let mut resolved = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let lookup = |name: &str, scopes: &Vec<Vec<usize>>, bindings: &Vec<Binding>| {
+            scopes.iter().rev().flat_map(|s| s.iter().rev()).copied().find(|&b| bindings[b].name == name)
+        };
+        let mut names = Vec::new();
+        let mut mention = |name: &str, scopes: &Vec<Vec<usize>>, bindings: &mut Vec<Binding>, assign: bool| {
+            let found = lookup(name, scopes, bindings);
+            match found {
+                Some(b) if assign => bindings[b].assigns.push(index),
+                Some(b) => bindings[b].uses.push(index),
+                None => errors.push(Violation {
+                    code: "E0425",
+                    message: format!("cannot find value `{}` in this scope", name),
+                    line: line.number,
+                    notes: Vec::new(),
+                }),
+            }
+            names.push(found);
+        };
+        match &line.stmt {
+            Stmt::Let { name, mutable, init } => {
+                if let Some(Expr::Borrow { target, .. } | Expr::Name(target)) = init {
+                    mention(target, &scopes, &mut bindings, false);
+                }
+                bindings.push(Binding {
+                    name: name.clone(),
+                    mutable: *mutable,
+                    loans: Vec::new(),
+                    initialized: false,
+                    moved: None,
+                    uses: Vec::new(),
+                    assigns: Vec::new(),
+                    declared: index,
+                });
+                scopes.last_mut().expect("the outer scope is never closed").push(bindings.len() - 1);
+                names.push(Some(bindings.len() - 1));
+            }
+            Stmt::Assign { name, value } => {
+                if let Expr::Borrow { target, .. } | Expr::Name(target) = value {
+                    mention(target, &scopes, &mut bindings, false);
+                }
+                mention(name, &scopes, &mut bindings, true);
+            }
+            Stmt::Use(name) | Stmt::Write(name) | Stmt::Drop(name) => mention(name, &scopes, &mut bindings, false),
+            Stmt::Open => scopes.push(Vec::new()),
+            // the bindings going out of scope stand in for names at a closing brace
+            Stmt::Close => names.extend(scopes.pop().expect("parse balances the braces").into_iter().map(Some)),
+        }
+        resolved.push(names);
+    }
+    (bindings, resolved, errors)
+}
+
+struct Checker<'p> {
+    lines: &'p [Line],
+    bindings: Vec<Binding>,
+    loans: Vec<Loan>,
+    violations: Vec<Violation>,
+}
+
+impl Checker<'_> {
+    /// The next use of the loan after statement `at`, if any holder still reads it
+    fn used_after(&self, loan: usize, at: usize) -> Option<usize> {
+        self.loans[loan].holders.iter()
+            .filter_map(|&(holder, from)| {
+                let binding = &self.bindings[holder];
+                let reassigned = binding.assigns.iter().copied().find(|&a| a > from).unwrap_or(usize::MAX);
+                binding.uses.iter().copied().filter(|&u| u > at && u > from && u <= reassigned).max()
+            })
+            .max()
+    }
+
+    fn live_loans(&self, owner: usize, at: usize) -> Vec<(usize, usize)> {
+        (0..self.loans.len()).filter(|&l| self.loans[l].owner == owner)
+                             .filter_map(|l| self.used_after(l, at).map(|used| (l, used)))
+                             .collect()
+    }
+
+    fn report(&mut self, code: &'static str, message: String, at: usize, loan: Option<(usize, usize)>) {
+        let mut notes = Vec::new();
+        if let Some((loan, used)) = loan {
+            let owner = &self.bindings[self.loans[loan].owner].name;
+            let kind = if self.loans[loan].mutable { "mutable borrow" } else { "borrow" };
+            notes.push((self.lines[self.loans[loan].created].number, format!("{} of `{}` occurs here", kind, owner)));
+            notes.push((self.lines[used].number, format!("{} later used here", kind)));
+        }
+        self.violations.push(Violation { code, message, line: self.lines[at].number, notes });
+    }
+
+    fn check_moved(&mut self, binding: usize, at: usize, what: &str) -> bool {
+        if let Some(moved) = self.bindings[binding].moved {
+            let name = self.bindings[binding].name.clone();
+            self.violations.push(Violation {
+                code: "E0382",
+                message: format!("{} of moved value: `{}`", what, name),
+                line: self.lines[at].number,
+                notes: vec![(self.lines[moved].number, "value moved here".to_string())],
+            });
+            return true;
+        }
+        if !self.bindings[binding].initialized {
+            let name = self.bindings[binding].name.clone();
+            self.report("E0381", format!("used binding `{}` isn't initialized", name), at, None);
+            return true;
+        }
+        false
+    }
+
+    fn borrow(&mut self, holder: usize, owner: usize, mutable: bool, at: usize) {
+        let name = self.bindings[owner].name.clone();
+        if self.check_moved(owner, at, "borrow") {
+            return;
+        }
+        if mutable && !self.bindings[owner].mutable {
+            self.report("E0596", format!("cannot borrow `{}` as mutable, as it is not declared as mutable", name), at, None);
+        }
+        for (loan, used) in self.live_loans(owner, at) {
+            match (mutable, self.loans[loan].mutable) {
+                (false, false) => {}
+                (true, true) => self.report("E0499", format!("cannot borrow `{}` as mutable more than once at a time", name),
+                                            at, Some((loan, used))),
+                (true, false) => self.report("E0502", format!("cannot borrow `{}` as mutable because it is also borrowed as immutable", name),
+                                             at, Some((loan, used))),
+                (false, true) => self.report("E0502", format!("cannot borrow `{}` as immutable because it is also borrowed as mutable", name),
+                                             at, Some((loan, used))),
+            }
+        }
+        self.loans.push(Loan { owner, mutable, created: at, holders: vec![(holder, at)] });
+        let loan = self.loans.len() - 1;
+        // `let r = &m` where m is itself a reference, r reaches x through m
+        // so m's loan of x has to stay alive as long as r is used
+        let through = self.bindings[owner].loans.clone();
+        for &reached in &through {
+            self.loans[reached].holders.push((holder, at));
+        }
+        self.bindings[holder].loans = std::iter::once(loan).chain(through).collect();
+        self.bindings[holder].initialized = true;
+    }
+
+    /// Shared references are Copy, owned values and &mut references are not
+    fn is_copy(&self, binding: usize) -> bool {
+        self.bindings[binding].loans.first().is_some_and(|&loan| !self.loans[loan].mutable)
+    }
+
+    /// `let y = x` or `y = x`, a copy for shared references and a move for everything else
+    fn transfer(&mut self, to: usize, from: usize, at: usize) {
+        if self.check_moved(from, at, "use") {
+            return;
+        }
+        let loans = self.bindings[from].loans.clone();
+        if loans.is_empty() {
+            self.move_out(from, at);
+        } else if !self.is_copy(from) {
+            // nothing can borrow from the &mut itself, so there is no E0505 to check for
+            self.bindings[from].moved = Some(at);
+        }
+        for &loan in &loans {
+            self.loans[loan].holders.push((to, at));
+        }
+        self.bindings[to].loans = loans;
+        self.bindings[to].initialized = true;
+    }
+
+    fn move_out(&mut self, binding: usize, at: usize) {
+        let name = self.bindings[binding].name.clone();
+        if let Some(&(loan, used)) = self.live_loans(binding, at).first() {
+            self.report("E0505", format!("cannot move out of `{}` because it is borrowed", name), at, Some((loan, used)));
+        }
+        self.bindings[binding].moved = Some(at);
+    }
+
+    fn statement(&mut self, at: usize, names: &[Option<usize>]) {
+        let stmt = self.lines[at].stmt.clone();
+        match stmt {
+            Stmt::Let { init, .. } => {
+                let this = names.last().copied().flatten().expect("let always binds");
+                self.bindings[this].initialized = init.is_some();
+                match (init, names.first().copied().flatten()) {
+                    (Some(Expr::Borrow { mutable, .. }), Some(target)) => self.borrow(this, target, mutable, at),
+                    (Some(Expr::Name(_)), Some(target)) => self.transfer(this, target, at),
+                    _ => {}
+                }
+            }
+            Stmt::Assign { value, .. } => {
+                let (target, this) = match names {
+                    [Some(target), Some(this)] => (Some(*target), *this),
+                    [Some(this)] => (None, *this),
+                    _ => return,
+                };
+                if self.bindings[this].initialized && !self.bindings[this].mutable {
+                    let binding = &self.bindings[this];
+                    let name = binding.name.clone();
+                    // the let itself unless it was `let r;`, then the first `r = ...`
+                    let first = match &self.lines[binding.declared].stmt {
+                        Stmt::Let { init: Some(_), .. } => binding.declared,
+                        _ => binding.assigns.iter().copied().min().unwrap_or(at),
+                    };
+                    let first = self.lines[first].number;
+                    self.violations.push(Violation {
+                        code: "E0384",
+                        message: format!("cannot assign twice to immutable variable `{}`", name),
+                        line: self.lines[at].number,
+                        notes: vec![(first, "first assignment".to_string())],
+                    });
+                }
+                match (value, target) {
+                    (Expr::Borrow { mutable, .. }, Some(target)) => self.borrow(this, target, mutable, at),
+                    (Expr::Name(_), Some(target)) => self.transfer(this, target, at),
+                    (Expr::New, _) => {
+                        self.bindings[this].loans.clear();
+                        self.bindings[this].initialized = true;
+                        self.bindings[this].moved = None;
+                    }
+                    _ => {}
+                }
+            }
+            Stmt::Use(_) => {
+                let Some(Some(this)) = names.first().copied() else { return };
+                if self.check_moved(this, at, "use") {
+                    return;
+                }
+                let name = self.bindings[this].name.clone();
+                for (loan, used) in self.live_loans(this, at) {
+                    if self.loans[loan].mutable {
+                        self.report("E0502", format!("cannot borrow `{}` as immutable because it is also borrowed as mutable", name),
+                                    at, Some((loan, used)));
+                    }
+                }
+            }
+            Stmt::Write(_) => {
+                let Some(Some(this)) = names.first().copied() else { return };
+                let through = self.bindings[this].loans.clone();
+                if through.is_empty() {
+                    // a temporary &mut of the owner, just like calling push_str
+                    let temporary = self.bindings.len();
+                    self.bindings.push(Binding {
+                        name: String::new(),
+                        mutable: false,
+                        loans: Vec::new(),
+                        initialized: false,
+                        moved: None,
+                        uses: Vec::new(),
+                        assigns: Vec::new(),
+                        declared: at,
+                    });
+                    self.borrow(temporary, this, true, at);
+                } else if self.check_moved(this, at, "use") {
+                } else if through.iter().any(|&l| !self.loans[l].mutable) {
+                    let name = self.bindings[this].name.clone();
+                    self.report("E0596", format!("cannot borrow `*{}` as mutable, as it is behind a `&` reference", name), at, None);
+                }
+            }
+            Stmt::Drop(_) => {
+                let Some(Some(this)) = names.first().copied() else { return };
+                if !self.check_moved(this, at, "use") && !self.is_copy(this) {
+                    self.move_out(this, at);
+                }
+            }
+            Stmt::Open => {}
+            Stmt::Close => {
+                for binding in names.iter().copied().flatten() {
+                    if self.bindings[binding].moved.is_some() {
+                        continue;
+                    }
+                    if let Some(&(loan, used)) = self.live_loans(binding, at).first() {
+                        let name = self.bindings[binding].name.clone();
+                        self.report("E0597", format!("`{}` does not live long enough", name), at, Some((loan, used)));
+                    }
+                    self.bindings[binding].moved = Some(at);
+                }
+            }
+        }
+    }
+}
+
+/// Checks a program, returning every violation found in source order.
+pub(crate) fn check(program: &str) -> Result<Vec<Violation>, String> {
+    let lines = parse(program)?;
+    let (bindings, resolved, mut violations) = resolve(&lines);
+    let mut checker = Checker { lines: &lines, bindings, loans: Vec::new(), violations: Vec::new() };
+    for (at, names) in resolved.iter().enumerate() {
+        checker.statement(at, names);
+    }
+    violations.append(&mut checker.violations);
+    violations.sort_by_key(|v| v.line);
+    Ok(violations)
+}
+
+pub(crate) fn format(program: &str, violation: &Violation) -> String {
+    let source = |line: usize| program.lines().nth(line - 1).unwrap_or("").trim();
+    let mut text = format!("error[{}]: {}\n --> line {}\n", violation.code, violation.message, violation.line);
+    let mut lines: Vec<(usize, String)> = violation.notes.clone();
+    lines.push((violation.line, String::from("<- here")));
+    lines.sort_by_key(|(line, _)| *line);
+    for (line, note) in lines {
+        text.push_str(&format!("{:>4} | {:<30} {}\n", line, source(line), note));
+    }
+    text
+}
+
+pub(crate) fn sample(name: &str) -> Result<&'static str, String> {
+    SAMPLES.iter().find(|(sample, _)| *sample == name).map(|(_, program)| *program)
+           .ok_or_else(|| format!("no sample named '{}', run borrowck with no arguments to list them", name))
+}
+
+pub(crate) fn print_samples() {
+    for (name, program) in SAMPLES {
+        let summary = program.lines().next().unwrap_or("").trim_start_matches("// ");
+        println!("  {:<26} {}", name, summary);
+    }
+}
+
+pub(crate) fn run(program: &str) -> Result<(), String> {
+    let violations = check(program)?;
+    for violation in &violations {
+        println!("{}", format(program, violation));
+    }
+    match violations.len() {
+        0 => {
+            println!("no borrow errors");
+            Ok(())
+        }
+        n => Err(format!("could not compile due to {} previous error{}", n, if n == 1 { "" } else { "s" })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(program: &str) -> Vec<&'static str> {
+        check(program).unwrap().iter().map(|v| v.code).collect()
+    }
+
+    #[test]
+    fn test_samples() {
+        let expected = [
+            ("shared", vec![]),
+            ("drop-while-borrowed", vec!["E0505"]),
+            ("shared-while-mutable", vec!["E0502"]),
+            ("nll", vec![]),
+            ("does-not-live-long-enough", vec!["E0597"]),
+        ];
+        for (name, codes_expected) in expected {
+            let (_, program) = SAMPLES.iter().find(|(n, _)| *n == name).unwrap();
+            assert_eq!(codes_expected, codes(program), "sample {}", name);
+        }
+    }
+
+    #[test]
+    fn test_mutable_rules() {
+        assert_eq!(vec!["E0596"], codes("let x = new; let m = &mut x; use m;"));
+        assert_eq!(vec!["E0499"], codes("let mut x = new; let a = &mut x; let b = &mut x; use a; use b;"));
+        assert_eq!(Vec::<&str>::new(), codes("let mut x = new; let a = &mut x; use a; let b = &mut x; use b;"));
+        assert_eq!(vec!["E0502"], codes("let mut x = new; let r = &x; write x; use r;"));
+        assert_eq!(vec!["E0596"], codes("let mut x = new; let r = &x; write r;"));
+        // m is a reference, but the binding itself still has to be mut to be borrowed mutably
+        assert_eq!(vec!["E0596"], codes("let mut x = new; let m = &mut x; let n = &mut m; write n;"));
+    }
+
+    #[test]
+    fn test_moves_and_copies() {
+        assert_eq!(vec!["E0382"], codes("let x = new; let y = x; use x;"));
+        assert_eq!(vec!["E0505"], codes("let x = new; let r = &x; let r2 = r; drop x; use r2;"));
+        assert_eq!(vec!["E0381"], codes("let r; use r;"));
+        assert_eq!(Vec::<&str>::new(), codes("let mut x = new; let r = &x; let r2 = r; use r; use r2;"));
+        assert_eq!(vec!["E0425"], codes("{ let x = new; } use x;"));
+    }
+
+    #[test]
+    fn test_mutable_references_are_moved() {
+        assert_eq!(vec!["E0382"], codes("let mut x = new; let m = &mut x; let m2 = m; write m2; use m;"));
+        assert_eq!(vec!["E0382"], codes("let mut x = new; let m = &mut x; drop m; write m;"));
+        // the loan moves with it, so x is still borrowed while m2 is used
+        assert_eq!(vec!["E0502"], codes("let mut x = new; let m = &mut x; let m2 = m; use x; write m2;"));
+    }
+
+    #[test]
+    fn test_borrow_through_a_reference_keeps_the_loan() {
+        assert_eq!(vec!["E0499"], codes("let mut x = new; let m = &mut x; let r = &m; write x; use r;"));
+        assert_eq!(vec!["E0502"], codes("let mut x = new; let s = &x; let r = &s; write x; use r;"));
+        assert_eq!(Vec::<&str>::new(), codes("let mut x = new; let m = &mut x; let r = &m; use r; write x;"));
+        assert_eq!(vec!["E0597"], codes("let r; let mut x = new; { let m = &mut x; r = &m; } use r;"));
+    }
+
+    #[test]
+    fn test_assign_twice_to_immutable() {
+        assert_eq!(vec!["E0384"], codes("let x = new; x = new;"));
+        assert_eq!(vec!["E0384"], codes("let a = new; let b = new; let r; r = &a; r = &b; use r;"));
+        assert_eq!(Vec::<&str>::new(), codes("let a = new; let b = new; let mut r = &a; r = &b; use r;"));
+        assert_eq!(Vec::<&str>::new(), codes("let a = new; let r; r = &a; use r;"));
+
+        let program = "let a = new;\nlet r;\nr = &a;\nr = &a;";
+        let text = format(program, &check(program).unwrap()[0]);
+        assert!(text.starts_with("error[E0384]: cannot assign twice to immutable variable `r`"), "{}", text);
+        assert!(text.contains("   3 | r = &a;"), "{}", text);
+    }
+
+    #[test]
+    fn test_message_points_at_borrow_and_use() {
+        let program = "let data = new;\nlet r = &data;\ndrop data;\nuse r;";
+        let violations = check(program).unwrap();
+        let text = format(program, &violations[0]);
+        assert!(text.starts_with("error[E0505]: cannot move out of `data` because it is borrowed"), "{}", text);
+        assert!(text.contains("borrow of `data` occurs here"), "{}", text);
+        assert!(text.contains("borrow later used here"), "{}", text);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(check("let x = new").is_err());
+        assert!(check("let x = &&y;").is_err());
+        assert!(check("jump x;").is_err());
+    }
+}
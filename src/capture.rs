@@ -0,0 +1,23 @@
+use std::process::Command;
+use crate::lesson::{banner, Example, Lesson};
+
+/// Runs one example in a child process and returns the lines it printed after its banner.
+/// The lessons print straight to stdout, a fresh process is the simplest way to collect it.
+pub(crate) fn example_output(lesson: &dyn Lesson, example: &Example) -> Result<Vec<String>, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("unable to find our own binary: {}", e))?;
+    let id = format!("{}.{}", lesson.id(), example.id);
+    let output = Command::new(&exe)
+        .args(["run", &id])
+        .output()
+        .map_err(|e| format!("unable to run {}: {}", exe.display(), e))?;
+    if !output.status.success() {
+        return Err(format!("example {} failed: {}", id, String::from_utf8_lossy(&output.stderr).trim_end()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let banner = banner(lesson, example);
+    Ok(stdout.lines()
+             .skip_while(|line| *line != banner)
+             .skip(1)
+             .map(String::from)
+             .collect())
+}
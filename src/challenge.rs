@@ -0,0 +1,286 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use crate::compile_fail::CASES;
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{self, Score};
+use crate::rustc;
+
+pub(crate) const SCOREBOARD: &str = "challenge_scores.txt";
+
+/// A program for the learner to judge, rustc has the final word on whether it compiles.
+pub(crate) struct Challenge {
+    pub(crate) name: &'static str,
+    pub(crate) lesson: u32,
+    pub(crate) example: &'static str,
+    pub(crate) source: &'static str,
+}
+
+/// The twins of the compile-fail cases, each one line away from its broken version.
+static PASSES: &[Challenge] = &[
+    Challenge {
+        name: "drop after the last use",
+        lesson: 1,
+        example: "1",
+        source: include_str!("../compile_pass/lesson_1_example_1_drop_after_use.rs"),
+    },
+    Challenge {
+        name: "shared borrow after the mutable one",
+        lesson: 1,
+        example: "2",
+        source: include_str!("../compile_pass/lesson_1_example_2_shared_after_mut.rs"),
+    },
+    Challenge {
+        name: "drop b once the struct is done",
+        lesson: 2,
+        example: "2",
+        source: include_str!("../compile_pass/lesson_2_example_2_drop_b_after_use.rs"),
+    },
+    Challenge {
+        name: "consume_return_a outlives b_string",
+        lesson: 2,
+        example: "3.5",
+        source: include_str!("../compile_pass/lesson_2_example_3_5_consume_return_a.rs"),
+    },
+    Challenge {
+        name: "push_str after the guard",
+        lesson: 3,
+        example: "1",
+        source: include_str!("../compile_pass/lesson_3_example_1_push_str_after_use.rs"),
+    },
+    Challenge {
+        name: "boxed closure owns its capture",
+        lesson: 5,
+        example: "4",
+        source: include_str!("../compile_pass/lesson_5_example_4_closure_owns_skip.rs"),
+    },
+    Challenge {
+        name: "feed a 'static value",
+        lesson: 6,
+        example: "2",
+        source: include_str!("../compile_pass/lesson_6_example_2_feed_static_value.rs"),
+    },
+    Challenge {
+        name: "read another field while one is captured",
+        lesson: 7,
+        example: "2",
+        source: include_str!("../compile_pass/lesson_7_example_2_read_other_field.rs"),
+    },
+    Challenge {
+        name: "move the Vec into the thread",
+        lesson: 8,
+        example: "1",
+        source: include_str!("../compile_pass/lesson_8_example_1_spawn_moves.rs"),
+    },
+    Challenge {
+        name: "scoped threads share a borrow",
+        lesson: 8,
+        example: "3",
+        source: include_str!("../compile_pass/lesson_8_example_3_shared_readers.rs"),
+    },
+    Challenge {
+        name: "move before borrowing itself",
+        lesson: 9,
+        example: "1",
+        source: include_str!("../compile_pass/lesson_9_example_1_move_before_borrow.rs"),
+    },
+];
+
+/// Every challenge for the selected examples, broken and working variants side by side.
+pub(crate) fn challenges(selected: &[(&'static dyn Lesson, &'static Example)]) -> Vec<Challenge> {
+    let broken = CASES.iter().map(|case| Challenge {
+        name: case.name,
+        lesson: case.lesson,
+        example: case.example,
+        source: case.source,
+    });
+    let working = PASSES.iter().map(|pass| Challenge { ..*pass });
+    broken.chain(working)
+          .filter(|c| selected.iter().any(|(l, e)| l.id() == c.lesson && e.id == c.example))
+          .collect()
+}
+
+/// The snippet as shown to the learner, comments would give the answer away.
+pub(crate) fn without_comments(source: &str) -> String {
+    let mut shown = Vec::new();
+    for line in source.lines() {
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut cut = line.len();
+        for (i, c) in line.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_string => escaped = true,
+                '"' => in_string = !in_string,
+                '/' if !in_string && line[i..].starts_with("//") => {
+                    cut = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let code = line[..cut].trim_end();
+        if code.is_empty() && cut < line.len() {
+            continue; // the whole line was a comment
+        }
+        shown.push(code);
+    }
+    shown.join("\n")
+}
+
+/// Right answers and attempts per challenge, kept between sessions.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Scoreboard {
+    entries: Vec<(String, u32, u32)>,
+}
+
+impl Scoreboard {
+    /// A missing file is an empty scoreboard, everyone starts somewhere.
+    pub(crate) fn load(path: &Path) -> Result<Scoreboard, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Scoreboard::default()),
+            Err(e) => return Err(format!("unable to read {}: {}", path.display(), e)),
+        };
+        let mut board = Scoreboard::default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let (fields, name) = line.split_once(" challenge=")
+                                     .ok_or_else(|| format!("scoreboard line is missing challenge: {}", line))?;
+            let number = |key: &str| {
+                fields.split_whitespace()
+                      .find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))
+                      .and_then(|n| n.parse().ok())
+                      .ok_or_else(|| format!("scoreboard line has no {}: {}", key, line))
+            };
+            board.entries.push((name.to_string(), number("right")?, number("tries")?));
+        }
+        Ok(board)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<(), String> {
+        let text: String = self.entries.iter()
+                                       .map(|(name, right, tries)| format!("right={} tries={} challenge={}\n", right, tries, name))
+                                       .collect();
+        fs::write(path, text).map_err(|e| format!("unable to write {}: {}", path.display(), e))
+    }
+
+    pub(crate) fn record(&mut self, name: &str, right: bool) {
+        let index = match self.entries.iter().position(|(n, _, _)| n == name) {
+            Some(index) => index,
+            None => {
+                self.entries.push((name.to_string(), 0, 0));
+                self.entries.len() - 1
+            }
+        };
+        let (_, rights, tries) = &mut self.entries[index];
+        *rights += u32::from(right);
+        *tries += 1;
+    }
+
+    pub(crate) fn get(&self, name: &str) -> (u32, u32) {
+        self.entries.iter().find(|(n, _, _)| n == name).map_or((0, 0), |(_, right, tries)| (*right, *tries))
+    }
+}
+
+fn io_error(e: io::Error) -> String {
+    format!("challenge stopped: {}", e)
+}
+
+/// Shows each snippet, takes a prediction, then lets rustc settle it. The
+/// scoreboard is saved after every answer so quitting part way loses nothing.
+pub(crate) fn run<R: BufRead, W: Write>(challenges: &[Challenge], scores: &Path,
+                                        input: &mut R, output: &mut W) -> Result<Score, String> {
+    let mut board = Scoreboard::load(scores)?;
+    let mut score = Score::default();
+    for (number, challenge) in challenges.iter().enumerate() {
+        writeln!(output, "challenge {} of {}, lesson {} example {}", number + 1, challenges.len(),
+                 challenge.lesson, challenge.example).map_err(io_error)?;
+        // rustc judges exactly the text the learner was shown
+        let shown = without_comments(challenge.source);
+        for line in shown.lines() {
+            writeln!(output, "    {}", line).map_err(io_error)?;
+        }
+        let Some(predicted) = quiz::yes_no("does it compile? [y/n]: ", input, output).map_err(io_error)? else { break };
+        let compiled = rustc::check(&shown)?;
+        if compiled.success {
+            writeln!(output, "rustc: it compiles").map_err(io_error)?;
+        } else {
+            writeln!(output, "rustc:").map_err(io_error)?;
+            for line in compiled.stderr.trim_end().lines() {
+                writeln!(output, "    {}", line).map_err(io_error)?;
+            }
+        }
+        let right = predicted == compiled.success;
+        score.asked += 1;
+        score.correct += usize::from(right);
+        board.record(challenge.name, right);
+        board.save(scores)?;
+        let (rights, tries) = board.get(challenge.name);
+        writeln!(output, "{}, {} of {} right on '{}' so far\n", if right { "correct" } else { "not quite" },
+                 rights, tries, challenge.name).map_err(io_error)?;
+    }
+    writeln!(output, "score: {} of {}, saved in {}", score.correct, score.asked, scores.display()).map_err(io_error)?;
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::all_examples;
+    use crate::rustc::Scratch;
+
+    #[test]
+    fn test_working_twins_compile() {
+        for pass in PASSES {
+            let compiled = rustc::check(pass.source).unwrap();
+            assert!(compiled.success, "{}\n{}", pass.name, compiled.stderr);
+            assert!(CASES.iter().any(|c| c.lesson == pass.lesson && c.example == pass.example),
+                    "{} has no broken twin", pass.name);
+        }
+    }
+
+    #[test]
+    fn test_comments_are_hidden() {
+        let shown = without_comments("// the answer\nfn main() {\n    let s = \"//\"; // this will cause an error\n}");
+        assert_eq!("fn main() {\n    let s = \"//\";\n}", shown);
+        for challenge in challenges(&all_examples()) {
+            assert!(!without_comments(challenge.source).contains("error"), "{}", challenge.name);
+        }
+    }
+
+    #[test]
+    fn test_shown_text_gets_the_same_verdict() {
+        // stripping the comments must not change what rustc says about a snippet
+        for case in CASES {
+            let compiled = rustc::check(&without_comments(case.source)).unwrap();
+            assert!(compiled.error_codes().contains(&case.error), "{}\n{}", case.name, compiled.stderr);
+        }
+        for pass in PASSES {
+            let compiled = rustc::check(&without_comments(pass.source)).unwrap();
+            assert!(compiled.success, "{}\n{}", pass.name, compiled.stderr);
+        }
+    }
+
+    #[test]
+    fn test_scoreboard_persists() {
+        let scratch = Scratch::new().unwrap();
+        let path = scratch.path.join(SCOREBOARD);
+        let selected: Vec<_> = all_examples().into_iter().filter(|(l, e)| l.id() == 2 && e.id == "3.5").collect();
+        let mut list = challenges(&selected);
+        list.retain(|c| c.name.starts_with("consume_return"));
+        assert_eq!(2, list.len());
+
+        // consume_return_b is rejected and consume_return_a compiles, answer both as compiling
+        let mut output = Vec::new();
+        let score = run(&list, &path, &mut "y\ny\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(Score { correct: 1, asked: 2 }, score, "{}", output);
+        assert!(output.contains("error[E0597]"), "{}", output);
+        assert!(output.contains("snippet.rs"), "{}", output);
+
+        run(&list[..1], &path, &mut "n\n".as_bytes(), &mut Vec::new()).unwrap();
+        let board = Scoreboard::load(&path).unwrap();
+        assert_eq!((1, 2), board.get(list[0].name));
+        assert_eq!((1, 1), board.get(list[1].name));
+    }
+}
@@ -0,0 +1,437 @@
+use std::fmt;
+use std::path::PathBuf;
+use crate::challenge;
+use crate::exercises;
+use crate::handout;
+use crate::raffle::{self, Seed};
+
+pub(crate) const USAGE: &str = "\
+usage: learn-lifetimes [command]
+
+commands:
+  run [SELECTOR...] [--from SELECTOR] [--to SELECTOR] [--format text|json]
+                 run the chosen lessons or examples, all of them by default,
+                 json gives each example's ids, title, source and output lines
+  list           list every lesson and example id
+  verify         compile every 'this will cause an error' line and check its error code,
+                 then check the Rustonomicon elision table from lesson 1
+  raffle [--attendees N | --roster FILE] [--winners N] [--seed N | --seed-from TEXT] [--log FILE]
+                 draw distinct winners from the meetup attendees, recording the draw
+  raffle --replay [--log FILE] [--roster FILE]
+                 redraw every logged raffle and confirm the winners match, with --roster
+                 also confirm roster draws were made from that file
+  raffle stats [--attendees N | --roster FILE] [--winners N] [--draws N] [--seed N]
+                 simulate many draws and report how uniform the winners are
+  expand SIGNATURE [--lifetime-type NAME]...
+                 apply the elision rules from lesson 1 and print the expanded signature,
+                 --lifetime-type names a type like BufWriter whose lifetime is hidden
+  present [SELECTOR...] [--from SELECTOR] [--to SELECTOR]
+                 step through the examples one at a time, source above output,
+                 enter for next, b for back, j 2.3 to jump, q to quit
+  timeline SELECTOR
+                 draw an ASCII timeline of each value's scope and each borrow's live range
+  quiz [SELECTOR...] [--from SELECTOR] [--to SELECTOR]
+                 answer the questions the lessons ask about their examples
+  challenge [SELECTOR...] [--from SELECTOR] [--to SELECTOR] [--scores FILE]
+                 predict whether snippets compile, then see what rustc says,
+                 the scoreboard is kept in challenge_scores.txt
+  check LESSON [--file FILE]
+                 grade your answers to a lesson's exercises, exercises/workspace/lesson_N.rs by default,
+                 a fresh copy of the stubs is written there the first time
+  glossary [TERM]
+                 list the vocabulary from every lesson, or define one term
+  export [SELECTOR...] [--from SELECTOR] [--to SELECTOR] [--out DIR]
+                 write a Markdown and an HTML handout for each lesson, into handouts/ by default
+  borrowck FILE | --sample NAME | --program TEXT
+                 borrow check a program in the toy let/use/drop language, no argument
+                 lists the samples
+  help           show this message
+
+a SELECTOR is a lesson id or lesson.example id, eg 2 or 2.3.5 for lesson 2 example 3.5";
+
+pub(crate) enum Command {
+    Run { selection: Selection, format: Format },
+    List,
+    Verify,
+    Raffle(RaffleOptions),
+    Expand { signature: String, lifetime_types: Vec<String> },
+    Timeline(Selection),
+    Present(Selection),
+    Quiz(Selection),
+    Challenge { selection: Selection, scores: PathBuf },
+    Check { lesson: u32, file: PathBuf },
+    Glossary(Option<String>),
+    Export { selection: Selection, dir: PathBuf },
+    Borrowck(Program),
+    Help,
+}
+
+/// Where the toy borrow checker reads its program from
+#[derive(Debug, PartialEq)]
+pub(crate) enum Program {
+    File(PathBuf),
+    Sample(String),
+    Text(String),
+    /// No program given, list the samples instead
+    Samples,
+}
+
+pub(crate) struct RaffleOptions {
+    pub(crate) attendees: usize,
+    pub(crate) winners: usize,
+    pub(crate) seed: Option<Seed>,
+    /// CSV with name/email columns or one name per line, replaces --attendees
+    pub(crate) roster: Option<PathBuf>,
+    pub(crate) log: PathBuf,
+    pub(crate) mode: RaffleMode,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum RaffleMode {
+    Draw,
+    /// Redraw everything in the audit log
+    Replay,
+    /// Simulate many draws to check fairness
+    Stats { draws: u64 },
+}
+
+const STATS_DRAWS: u64 = 100_000;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Format {
+    Text,
+    Json,
+}
+
+/// Which examples to run, explicit selectors are unioned and then narrowed by --from/--to.
+#[derive(Default)]
+pub(crate) struct Selection {
+    pub(crate) selectors: Vec<Selector>,
+    pub(crate) from: Option<Selector>,
+    pub(crate) to: Option<Selector>,
+}
+
+/// A lesson id with an optional example id, "2.3.5" is lesson 2 example "3.5".
+#[derive(Debug, PartialEq)]
+pub(crate) struct Selector {
+    pub(crate) lesson: u32,
+    pub(crate) example: Option<String>,
+}
+
+impl Selector {
+    pub(crate) fn parse(text: &str) -> Result<Selector, String> {
+        let (lesson, example) = match text.split_once('.') {
+            Some((lesson, example)) => (lesson, Some(example)),
+            None => (text, None),
+        };
+        let lesson = lesson.parse::<u32>()
+                           .map_err(|_| format!("'{}' is not a lesson id", text))?;
+        match example {
+            Some("") => Err(format!("'{}' is missing an example id", text)),
+            example => Ok(Selector { lesson, example: example.map(String::from) }),
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.example {
+            Some(example) => write!(f, "{}.{}", self.lesson, example),
+            None => write!(f, "{}", self.lesson),
+        }
+    }
+}
+
+pub(crate) fn parse(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+    match args.next().map(String::as_str) {
+        None => Ok(Command::Run { selection: Selection::default(), format: Format::Text }),
+        Some("run") => parse_run_command(args),
+        Some("list") => no_more(args).map(|_| Command::List),
+        Some("verify") => no_more(args).map(|_| Command::Verify),
+        Some("raffle") => parse_raffle(args).map(Command::Raffle),
+        Some("expand") => parse_expand(args),
+        Some("timeline") => parse_run(args).map(Command::Timeline),
+        Some("present") => parse_run(args).map(Command::Present),
+        Some("quiz") => parse_run(args).map(Command::Quiz),
+        Some("challenge") => parse_challenge(args),
+        Some("check") => parse_check(args),
+        Some("glossary") => parse_glossary(args),
+        Some("export") => parse_export(args),
+        Some("borrowck") => parse_borrowck(args).map(Command::Borrowck),
+        Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command '{}'", other)),
+    }
+}
+
+fn parse_run<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Selection, String> {
+    let mut selection = Selection::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => selection.from = Some(Selector::parse(value(&mut args, arg)?)?),
+            "--to" => selection.to = Some(Selector::parse(value(&mut args, arg)?)?),
+            flag if flag.starts_with("--") => return Err(format!("unknown option '{}'", flag)),
+            selector => selection.selectors.push(Selector::parse(selector)?),
+        }
+    }
+    Ok(selection)
+}
+
+fn parse_run_command<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut format = Format::Text;
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = match value(&mut args, arg)? {
+                "text" => Format::Text,
+                "json" => Format::Json,
+                other => return Err(format!("--format is text or json, not '{}'", other)),
+            },
+            _ => rest.push(arg),
+        }
+    }
+    Ok(Command::Run { selection: parse_run(rest.into_iter())?, format })
+}
+
+fn parse_raffle<'a>(args: impl Iterator<Item = &'a String>) -> Result<RaffleOptions, String> {
+    let mut args = args.peekable();
+    let stats = args.next_if(|arg| arg.as_str() == "stats").is_some();
+    let mut options = RaffleOptions {
+        attendees: raffle::ATTENDEE_COUNT,
+        winners: raffle::WINNER_COUNT,
+        seed: None,
+        roster: None,
+        log: PathBuf::from(raffle::AUDIT_LOG),
+        mode: if stats { RaffleMode::Stats { draws: STATS_DRAWS } } else { RaffleMode::Draw },
+    };
+    let mut winners_given = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--attendees" => options.attendees = number(value(&mut args, arg)?, arg)?,
+            "--winners" => {
+                options.winners = number(value(&mut args, arg)?, arg)?;
+                winners_given = true;
+            }
+            "--seed" | "--seed-from" if options.seed.is_some() => {
+                return Err("give one of --seed and --seed-from, not both".to_string());
+            }
+            "--seed" => options.seed = Some(Seed::Number(number(value(&mut args, arg)?, arg)?)),
+            "--seed-from" => options.seed = Some(Seed::Phrase(value(&mut args, arg)?.to_string())),
+            "--roster" => options.roster = Some(PathBuf::from(value(&mut args, arg)?)),
+            "--log" => options.log = PathBuf::from(value(&mut args, arg)?),
+            "--replay" if !stats => options.mode = RaffleMode::Replay,
+            "--draws" if stats => options.mode = RaffleMode::Stats { draws: number(value(&mut args, arg)?, arg)? },
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+    // a replay draws as many winners as each log line recorded
+    if winners_given && options.mode == RaffleMode::Replay {
+        return Err("--winners can not be used with --replay, the log says how many were drawn".to_string());
+    }
+    Ok(options)
+}
+
+fn parse_expand<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut signature = None;
+    let mut lifetime_types = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lifetime-type" => lifetime_types.push(value(&mut args, arg)?.to_string()),
+            text if signature.is_none() => signature = Some(text.to_string()),
+            extra => return Err(format!("unexpected argument '{}', quote the signature", extra)),
+        }
+    }
+    let signature = signature.ok_or("expand needs a signature such as \"fn substr(s: &str, until: usize) -> &str\"")?;
+    Ok(Command::Expand { signature, lifetime_types })
+}
+
+fn parse_challenge<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut scores = PathBuf::from(challenge::SCOREBOARD);
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--scores" => scores = PathBuf::from(value(&mut args, arg)?),
+            _ => rest.push(arg),
+        }
+    }
+    Ok(Command::Challenge { selection: parse_run(rest.into_iter())?, scores })
+}
+
+fn parse_export<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut dir = PathBuf::from(handout::HANDOUT_DIR);
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => dir = PathBuf::from(value(&mut args, arg)?),
+            _ => rest.push(arg),
+        }
+    }
+    Ok(Command::Export { selection: parse_run(rest.into_iter())?, dir })
+}
+
+fn parse_check<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut lesson = None;
+    let mut file = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--file" => file = Some(PathBuf::from(value(&mut args, arg)?)),
+            flag if flag.starts_with("--") => return Err(format!("unknown option '{}'", flag)),
+            text if lesson.is_none() => lesson = Some(number(text, "check")?),
+            extra => return Err(format!("unexpected argument '{}'", extra)),
+        }
+    }
+    let lesson = lesson.ok_or("check needs a lesson id such as 3")?;
+    Ok(Command::Check { lesson, file: file.unwrap_or_else(|| exercises::default_file(lesson)) })
+}
+
+/// Terms may be several words, `glossary mutable reference` needs no quotes.
+fn parse_glossary<'a>(args: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let words: Vec<&str> = args.map(String::as_str).collect();
+    if let Some(flag) = words.iter().find(|word| word.starts_with("--")) {
+        return Err(format!("unknown option '{}'", flag));
+    }
+    Ok(Command::Glossary(if words.is_empty() { None } else { Some(words.join(" ")) }))
+}
+
+fn parse_borrowck<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<Program, String> {
+    let program = match args.next().map(String::as_str) {
+        None => Program::Samples,
+        Some(flag @ "--sample") => Program::Sample(value(&mut args, flag)?.to_string()),
+        Some(flag @ "--program") => Program::Text(value(&mut args, flag)?.to_string()),
+        Some(flag) if flag.starts_with("--") => return Err(format!("unknown option '{}'", flag)),
+        Some(file) => Program::File(PathBuf::from(file)),
+    };
+    no_more(args).map(|_| program)
+}
+
+fn number<T: std::str::FromStr>(text: &str, flag: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("{} expects a number, not '{}'", flag, text))
+}
+
+pub(crate) fn value<'a>(args: &mut impl Iterator<Item = &'a String>, flag: &str) -> Result<&'a str, String> {
+    args.next().map(String::as_str).ok_or_else(|| format!("{} needs a value", flag))
+}
+
+fn no_more<'a>(mut args: impl Iterator<Item = &'a String>) -> Result<(), String> {
+    match args.next() {
+        Some(extra) => Err(format!("unexpected argument '{}'", extra)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_selector_splits_on_first_dot() {
+        assert_eq!(Selector { lesson: 2, example: Some("3.5".into()) }, Selector::parse("2.3.5").unwrap());
+        assert_eq!(Selector { lesson: 1, example: None }, Selector::parse("1").unwrap());
+        assert!(Selector::parse("two").is_err());
+        assert!(Selector::parse("2.").is_err());
+    }
+
+    #[test]
+    fn test_parse_run_with_range() {
+        match parse(&args("run 1.2 --from 2 --to 3")).unwrap() {
+            Command::Run { selection, format } => {
+                assert_eq!(Format::Text, format);
+                assert_eq!(vec![Selector { lesson: 1, example: Some("2".into()) }], selection.selectors);
+                assert_eq!(Some(Selector { lesson: 2, example: None }), selection.from);
+                assert_eq!(Some(Selector { lesson: 3, example: None }), selection.to);
+            }
+            _ => panic!("expected run"),
+        }
+        assert!(parse(&args("run --to")).is_err());
+        assert!(matches!(parse(&args("run 2 --format json")).unwrap(), Command::Run { format: Format::Json, .. }));
+        assert!(parse(&args("run --format yaml")).is_err());
+        assert!(parse(&args("list extra")).is_err());
+        assert!(parse(&args("dance")).is_err());
+    }
+
+    #[test]
+    fn test_parse_raffle() {
+        match parse(&args("raffle --attendees 12 --winners 3 --seed 99")).unwrap() {
+            Command::Raffle(options) => {
+                assert_eq!(12, options.attendees);
+                assert_eq!(3, options.winners);
+                assert_eq!(Some(99), options.seed.map(|s| s.value()));
+                assert_eq!(RaffleMode::Draw, options.mode);
+            }
+            _ => panic!("expected raffle"),
+        }
+        assert!(parse(&args("raffle --winners two")).is_err());
+        assert!(parse(&args("raffle --seed 99 --seed-from october")).is_err());
+        assert!(parse(&args("raffle --seed-from october --seed 99")).is_err());
+        assert!(parse(&args("raffle --replay --winners 3")).is_err());
+        assert!(parse(&args("raffle --winners 3 --replay")).is_err());
+        match parse(&args("raffle stats --draws 500")).unwrap() {
+            Command::Raffle(options) => assert_eq!(RaffleMode::Stats { draws: 500 }, options.mode),
+            _ => panic!("expected raffle"),
+        }
+        assert!(parse(&args("raffle --draws 500")).is_err());
+    }
+
+    #[test]
+    fn test_parse_challenge() {
+        match parse(&args("challenge 2 --scores mine.txt --to 2.3")).unwrap() {
+            Command::Challenge { selection, scores } => {
+                assert_eq!(vec![Selector { lesson: 2, example: None }], selection.selectors);
+                assert_eq!(Some(Selector { lesson: 2, example: Some("3".into()) }), selection.to);
+                assert_eq!(PathBuf::from("mine.txt"), scores);
+            }
+            _ => panic!("expected challenge"),
+        }
+        assert!(parse(&args("challenge --scores")).is_err());
+    }
+
+    #[test]
+    fn test_parse_export() {
+        match parse(&args("export 1 --out talk")).unwrap() {
+            Command::Export { selection, dir } => {
+                assert_eq!(vec![Selector { lesson: 1, example: None }], selection.selectors);
+                assert_eq!(PathBuf::from("talk"), dir);
+            }
+            _ => panic!("expected export"),
+        }
+    }
+
+    #[test]
+    fn test_parse_check() {
+        match parse(&args("check 3")).unwrap() {
+            Command::Check { lesson, file } => {
+                assert_eq!(3, lesson);
+                assert_eq!(PathBuf::from("exercises/workspace/lesson_3.rs"), file);
+            }
+            _ => panic!("expected check"),
+        }
+        assert!(parse(&args("check")).is_err());
+        assert!(parse(&args("check three")).is_err());
+    }
+
+    #[test]
+    fn test_parse_glossary() {
+        match parse(&args("glossary mutable reference")).unwrap() {
+            Command::Glossary(term) => assert_eq!(Some("mutable reference".to_string()), term),
+            _ => panic!("expected glossary"),
+        }
+        assert!(matches!(parse(&args("glossary")).unwrap(), Command::Glossary(None)));
+    }
+
+    #[test]
+    fn test_parse_borrowck() {
+        match parse(&args("borrowck --sample nll")).unwrap() {
+            Command::Borrowck(program) => assert_eq!(Program::Sample("nll".into()), program),
+            _ => panic!("expected borrowck"),
+        }
+        match parse(&args("borrowck")).unwrap() {
+            Command::Borrowck(program) => assert_eq!(Program::Samples, program),
+            _ => panic!("expected borrowck"),
+        }
+        assert!(parse(&args("borrowck a.txt b.txt")).is_err());
+    }
+}
@@ -0,0 +1,325 @@
+use crate::lesson::lessons;
+use crate::rustc;
+
+/// A line the lessons claim "will cause an error", as a complete program that
+/// must be rejected by rustc with the given error code.
+pub(crate) struct Case {
+    pub(crate) name: &'static str,
+    pub(crate) lesson: u32,
+    pub(crate) example: &'static str,
+    /// The line as it appears in the lesson, so the two can not drift apart
+    pub(crate) claim: &'static str,
+    pub(crate) error: &'static str,
+    pub(crate) source: &'static str,
+}
+
+pub(crate) static CASES: &[Case] = &[
+    Case {
+        name: "drop while borrowed",
+        lesson: 1,
+        example: "1",
+        claim: "//drop(data); // this will cause an error",
+        error: "E0505",
+        source: include_str!("../compile_fail/lesson_1_example_1_drop.rs"),
+    },
+    Case {
+        name: "push_str on an immutable binding",
+        lesson: 1,
+        example: "1",
+        claim: "//data.push_str(\" World\"); // this will cause an error",
+        error: "E0596",
+        source: include_str!("../compile_fail/lesson_1_example_1_push_str.rs"),
+    },
+    Case {
+        name: "shared borrow while mutably borrowed",
+        lesson: 1,
+        example: "2",
+        claim: "//let reference2 = &data; /// this will cause an error",
+        error: "E0502",
+        source: include_str!("../compile_fail/lesson_1_example_2_shared_while_mut.rs"),
+    },
+    Case {
+        name: "return a reference to an owned argument",
+        lesson: 1,
+        example: "4",
+        claim: "my_text //we can NOT pass in owned object and return &my_text, why?",
+        error: "E0106",
+        source: include_str!("../compile_fail/lesson_1_example_4_return_owned.rs"),
+    },
+    Case {
+        name: "drop a value tied into a struct",
+        lesson: 2,
+        example: "2",
+        claim: "//drop(b); //this will cause an error",
+        error: "E0505",
+        source: include_str!("../compile_fail/lesson_2_example_2_drop_b.rs"),
+    },
+    Case {
+        name: "consume_return_b outlives b_string",
+        lesson: 2,
+        example: "3.5",
+        claim: "// a_ref = data.consume_return_b(); //this can not work. what was inferred?",
+        error: "E0597",
+        source: include_str!("../compile_fail/lesson_2_example_3_5_consume_return_b.rs"),
+    },
+    Case {
+        name: "process_a borrows data",
+        lesson: 2,
+        example: "3.5",
+        claim: "//a_ref = data.process_a(); //this is not allowed, it was not moved out of data",
+        error: "E0597",
+        source: include_str!("../compile_fail/lesson_2_example_3_5_process_a.rs"),
+    },
+    Case {
+        name: "push_str while guarded",
+        lesson: 3,
+        example: "1",
+        claim: "//s.push_str(\" New text\");",
+        error: "E0502",
+        source: include_str!("../compile_fail/lesson_3_example_1_push_str.rs"),
+    },
+    Case {
+        name: "return a reference to a local",
+        lesson: 3,
+        example: "5",
+        claim: "//   fn attempted_return_owned<'a>(text: String) -> &'a String {",
+        error: "E0515",
+        source: include_str!("../compile_fail/lesson_3_example_5_return_owned.rs"),
+    },
+    Case {
+        name: "names used after into_iter",
+        lesson: 4,
+        example: "1",
+        claim: "//println!(\"{:?}\", names); // this will cause an error",
+        error: "E0382",
+        source: include_str!("../compile_fail/lesson_4_example_1_use_after_into_iter.rs"),
+    },
+    Case {
+        name: "returned iterator hides a borrow",
+        lesson: 4,
+        example: "2",
+        claim: "//fn evens(numbers: &[i32]) -> impl Iterator<Item = i32> { // this will cause an error",
+        error: "E0700",
+        source: include_str!("../compile_fail/lesson_4_example_2_evens_without_bound.rs"),
+    },
+    Case {
+        name: "two iter_mut borrows at once",
+        lesson: 4,
+        example: "4",
+        claim: "//let second = numbers.iter_mut().nth(1).unwrap(); // this will cause an error",
+        error: "E0499",
+        source: include_str!("../compile_fail/lesson_4_example_4_two_iter_mut.rs"),
+    },
+    Case {
+        name: "borrowed value in a Box<dyn Display> field",
+        lesson: 5,
+        example: "1",
+        claim: "//shelf.items.push(Box::new(&name)); // this will cause an error",
+        error: "E0597",
+        source: include_str!("../compile_fail/lesson_5_example_1_box_borrow_static.rs"),
+    },
+    Case {
+        name: "borrowing closure boxed as 'static",
+        lesson: 5,
+        example: "4",
+        claim: "//let pipeline = StaticPipeline { step: Box::new(move |s| &s[*skip_ref..]) }; // this will cause an error",
+        error: "E0597",
+        source: include_str!("../compile_fail/lesson_5_example_4_borrowed_closure_static.rs"),
+    },
+    Case {
+        name: "a short borrow stored in a 'static variable",
+        lesson: 6,
+        example: "1",
+        claim: "//long = &local; // this will cause an error",
+        error: "E0597",
+        source: include_str!("../compile_fail/lesson_6_example_1_short_into_static.rs"),
+    },
+    Case {
+        name: "feeding a short &str through &mut &'static str",
+        lesson: 6,
+        example: "2",
+        claim: "//feed(&mut pet, spike.as_str()); // this will cause an error",
+        error: "E0597",
+        source: include_str!("../compile_fail/lesson_6_example_2_feed_short_value.rs"),
+    },
+    Case {
+        name: "shrinking a Cell<&'static str>",
+        lesson: 6,
+        example: "3",
+        claim: "//let short: &Cell<&str> = &cell; short.set(&local); // this will cause an error",
+        error: "E0597",
+        source: include_str!("../compile_fail/lesson_6_example_3_shrink_cell.rs"),
+    },
+    Case {
+        name: "fn(&'static str) used as fn(&str)",
+        lesson: 6,
+        example: "4",
+        claim: "//let handler: fn(&str) = print_static; // this will cause an error",
+        error: "E0308",
+        source: include_str!("../compile_fail/lesson_6_example_4_static_fn_as_any.rs"),
+    },
+    Case {
+        name: "drop under a PhantomData view",
+        lesson: 6,
+        example: "5",
+        claim: "//drop(data); // this will cause an error",
+        error: "E0505",
+        source: include_str!("../compile_fail/lesson_6_example_5_drop_under_view.rs"),
+    },
+    Case {
+        name: "push_str while a closure reads name",
+        lesson: 7,
+        example: "1",
+        claim: "//name.push_str(\" the crab\"); // this will cause an error",
+        error: "E0502",
+        source: include_str!("../compile_fail/lesson_7_example_1_push_while_captured.rs"),
+    },
+    Case {
+        name: "whole struct while a field is captured",
+        lesson: 7,
+        example: "2",
+        claim: "//println!(\"{}\", player.describe()); // this will cause an error",
+        error: "E0502",
+        source: include_str!("../compile_fail/lesson_7_example_2_whole_struct_while_field_captured.rs"),
+    },
+    Case {
+        name: "returned closure borrows a local",
+        lesson: 7,
+        example: "3",
+        claim: "//|| text.len() // this will cause an error",
+        error: "E0373",
+        source: include_str!("../compile_fail/lesson_7_example_3_borrow_outlives_function.rs"),
+    },
+    Case {
+        name: "returned closure hides a borrow",
+        lesson: 7,
+        example: "4",
+        claim: "//fn prefixer(prefix: &str) -> impl Fn(&str) -> String { // this will cause an error",
+        error: "E0700",
+        source: include_str!("../compile_fail/lesson_7_example_4_prefixer_without_bound.rs"),
+    },
+    Case {
+        name: "read between FnMut calls",
+        lesson: 7,
+        example: "5",
+        claim: "//println!(\"{}\", count); // this will cause an error",
+        error: "E0502",
+        source: include_str!("../compile_fail/lesson_7_example_5_read_between_calls.rs"),
+    },
+    Case {
+        name: "spawned closure borrows a local",
+        lesson: 8,
+        example: "1",
+        claim: "//let handle = thread::spawn(|| scores.iter().sum::<i32>()); // this will cause an error",
+        error: "E0373",
+        source: include_str!("../compile_fail/lesson_8_example_1_spawn_borrows.rs"),
+    },
+    Case {
+        name: "moving a reference into a spawned thread",
+        lesson: 8,
+        example: "2",
+        claim: "//let handle = thread::spawn(move || view.len()); // this will cause an error",
+        error: "E0597",
+        source: include_str!("../compile_fail/lesson_8_example_2_move_a_reference.rs"),
+    },
+    Case {
+        name: "clear while scoped writers hold chunks",
+        lesson: 8,
+        example: "3",
+        claim: "//s.spawn(|| doubled.clear()); // this will cause an error",
+        error: "E0499",
+        source: include_str!("../compile_fail/lesson_8_example_3_clear_while_writers_run.rs"),
+    },
+    Case {
+        name: "Arc used after moving it into a thread",
+        lesson: 8,
+        example: "4",
+        claim: "//println!(\"{:?}\", first); // this will cause an error",
+        error: "E0382",
+        source: include_str!("../compile_fail/lesson_8_example_4_use_moved_arc.rs"),
+    },
+    Case {
+        name: "moving a struct that borrows itself",
+        lesson: 9,
+        example: "1",
+        claim: "//let moved = holding; // this will cause an error",
+        error: "E0505",
+        source: include_str!("../compile_fail/lesson_9_example_1_move_self_borrow.rs"),
+    },
+    Case {
+        name: "returning a struct that borrows itself",
+        lesson: 9,
+        example: "2",
+        claim: "//   fn hold<'a>(text: String) -> HoldingStruct<'a> {",
+        error: "E0515",
+        source: include_str!("../compile_fail/lesson_9_example_2_return_self_borrow.rs"),
+    },
+    Case {
+        name: "moving out of a Pin",
+        lesson: 9,
+        example: "4",
+        claim: "//let unpinned = *moved; // this will cause an error",
+        error: "E0507",
+        source: include_str!("../compile_fail/lesson_9_example_4_move_out_of_pin.rs"),
+    },
+    Case {
+        name: "smuggling a word out of with_words",
+        lesson: 9,
+        example: "5",
+        claim: "//moved.with_words(|words| found.push(words[0])); // this will cause an error",
+        error: "E0521",
+        source: include_str!("../compile_fail/lesson_9_example_5_smuggle_word.rs"),
+    },
+];
+
+/// Compiles the case and confirms rustc rejects it for the reason the lesson gives.
+pub(crate) fn verify(case: &Case) -> Result<(), String> {
+    let lesson = lessons().iter()
+                          .find(|l| l.id() == case.lesson)
+                          .ok_or_else(|| format!("there is no lesson {}", case.lesson))?;
+    if !lesson.source().lines().any(|line| line.trim() == case.claim) {
+        return Err(format!("lesson {} no longer contains: {}", case.lesson, case.claim));
+    }
+    let compiled = rustc::check(case.source)?;
+    if compiled.success {
+        return Err("compiled without error".to_string());
+    }
+    let codes = compiled.error_codes();
+    if codes.contains(&case.error) {
+        Ok(())
+    } else {
+        Err(format!("expected {} but rustc reported {:?}\n{}", case.error, codes, compiled.stderr))
+    }
+}
+
+/// Verifies every case, printing one line each, and fails if any case did not hold.
+pub(crate) fn verify_all() -> Result<(), String> {
+    let mut failed = 0;
+    for case in CASES {
+        match verify(case) {
+            Ok(()) => println!("ok     {}.{} {} {}", case.lesson, case.example, case.error, case.name),
+            Err(message) => {
+                failed += 1;
+                println!("FAILED {}.{} {} {}: {}", case.lesson, case.example, case.error, case.name, message);
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} of {} compile-fail cases did not hold", n, CASES.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_case_fails_with_expected_error() {
+        for case in CASES {
+            if let Err(message) = verify(case) {
+                panic!("{}.{} {}: {}", case.lesson, case.example, case.name, message);
+            }
+        }
+    }
+}
@@ -0,0 +1,483 @@
+// Applies the three elision rules from lesson 1 to a function signature and
+// writes out the fully expanded form, the same thing an IDE shows as inlay hints.
+//
+// 1. Each elided lifetime in input position becomes a distinct lifetime parameter.
+// 2. If there is exactly one input lifetime position (elided or not), that lifetime is
+//    assigned to all elided output lifetimes.
+// 3. If there are multiple input lifetime positions, but one of them is &self or &mut self,
+//    the lifetime of self is assigned to all elided output lifetimes.
+//
+// References inside fn pointer and Fn trait types such as `f: fn(&str) -> &str` are
+// left alone, they have an elision scope of their own.
+
+use crate::lesson_1_reference::{ElisionPair, RUSTONOMICON_ELISION};
+use crate::rustc;
+
+/// One token of the signature, text is a slice of the original so we know where to edit.
+struct Token<'s> {
+    text: &'s str,
+    start: usize,
+}
+
+impl Token<'_> {
+    fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+    fn is_ident(&self) -> bool {
+        self.text.starts_with(|c: char| c.is_alphabetic() || c == '_') && self.text != "_"
+    }
+    fn is_lifetime(&self) -> bool {
+        self.text.starts_with('\'')
+    }
+}
+
+fn tokenize(signature: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let bytes = signature.as_bytes();
+    let mut i = 0;
+    let word_end = |mut j: usize| {
+        while j < bytes.len() && (bytes[j].is_ascii_alphanumeric() || bytes[j] == b'_' || bytes[j] >= 0x80) {
+            j += 1;
+        }
+        j
+    };
+    while i < bytes.len() {
+        let c = bytes[i];
+        let end = if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if c.is_ascii_alphanumeric() || c == b'_' || c >= 0x80 {
+            word_end(i)
+        } else if c == b'\'' {
+            let end = word_end(i + 1);
+            if end == i + 1 {
+                return Err(format!("expected a lifetime name after ' at {}", i));
+            }
+            end
+        } else if c == b'"' {
+            let close = signature[i + 1..].find('"').ok_or("unterminated string")?;
+            i + close + 2
+        } else if signature[i..].starts_with("->") || signature[i..].starts_with("::") {
+            i + 2
+        } else {
+            i + 1
+        };
+        tokens.push(Token { text: &signature[i..end], start: i });
+        i = end;
+    }
+    Ok(tokens)
+}
+
+/// A place in a type where a lifetime appears, or is elided and must be filled in.
+enum Slot {
+    Named(String),
+    /// `&T` or `&mut T`, the name goes right after the &
+    Reference { at: usize },
+    /// `Foo<'_>`
+    Placeholder { start: usize, end: usize },
+    /// `BufWriter` written without its lifetime argument
+    Path { at: usize },
+}
+
+impl Slot {
+    fn edit(&self, name: &str) -> Option<(usize, usize, String)> {
+        match self {
+            Slot::Named(_) => None,
+            Slot::Reference { at } => Some((*at, *at, format!("{} ", name))),
+            Slot::Placeholder { start, end } => Some((*start, *end, name.to_string())),
+            Slot::Path { at } => Some((*at, *at, format!("<{}>", name))),
+        }
+    }
+}
+
+/// The part of the signature holding one parameter or the return type.
+struct Region {
+    start: usize,
+    end: usize,
+    slots: Vec<Slot>,
+    is_self_ref: bool,
+}
+
+pub(crate) enum Expanded {
+    Legal { signature: String, steps: Vec<String> },
+    Illegal { reason: String, steps: Vec<String> },
+}
+
+/// Knows which type names carry a lifetime parameter, since a bare `BufWriter`
+/// hides it and there is no way to tell from the signature alone.
+#[derive(Default)]
+pub(crate) struct Expander {
+    lifetime_types: Vec<String>,
+}
+
+impl Expander {
+    pub(crate) fn new() -> Expander {
+        Expander::default()
+    }
+
+    pub(crate) fn with_lifetime_type(mut self, name: &str) -> Expander {
+        self.lifetime_types.push(name.to_string());
+        self
+    }
+
+    pub(crate) fn expand(&self, signature: &str) -> Result<Expanded, String> {
+        let tokens = tokenize(signature)?;
+        let mut i = tokens.iter()
+                          .position(|t| t.text == "fn")
+                          .ok_or("expected a signature starting with fn")?
+            + 1;
+        let name = tokens.get(i).filter(|t| t.is_ident()).ok_or("expected a function name after fn")?;
+        i += 1;
+
+        // existing generics, new lifetimes are added in front of them
+        let mut declared = Vec::new();
+        let generics = if tokens.get(i).is_some_and(|t| t.text == "<") {
+            let close = matching(&tokens, i, "<", ">")?;
+            declared.extend(tokens[i + 1..close].iter().filter(|t| t.is_lifetime()).map(|t| t.text.to_string()));
+            let empty = close == i + 1;
+            let at = tokens[i].end();
+            i = close + 1;
+            Some((at, empty))
+        } else {
+            None
+        };
+
+        if tokens.get(i).is_none_or(|t| t.text != "(") {
+            return Err("expected ( after the function name".to_string());
+        }
+        let close = matching(&tokens, i, "(", ")")?;
+        let mut inputs = Vec::new();
+        let mut start = i + 1;
+        for j in i + 1..=close {
+            if j == close || (tokens[j].text == "," && depth(&tokens[i + 1..j]) == 0) {
+                if start < j {
+                    inputs.push(self.region(&tokens[start..j]));
+                }
+                start = j + 1;
+            }
+        }
+        i = close + 1;
+
+        let output = if tokens.get(i).is_some_and(|t| t.text == "->") {
+            let end = tokens[i + 1..].iter()
+                                     .position(|t| matches!(t.text, "where" | "{" | ";"))
+                                     .map_or(tokens.len(), |p| p + i + 1);
+            Some(self.region(&tokens[i + 1..end]))
+        } else {
+            None
+        };
+
+        // rule 1, a fresh lifetime for every elided input
+        let mut fresh = names(&declared);
+        let mut edits = Vec::new();
+        let mut added = Vec::new();
+        let mut steps = Vec::new();
+        let mut input_lifetimes = Vec::new();
+        let mut self_lifetime = None;
+        for region in &inputs {
+            let mut region_edits = Vec::new();
+            for slot in &region.slots {
+                let lifetime = match slot {
+                    Slot::Named(name) => name.clone(),
+                    elided => {
+                        let name = fresh.next().expect("names never run out");
+                        region_edits.extend(elided.edit(&name));
+                        added.push(name.clone());
+                        name
+                    }
+                };
+                if region.is_self_ref && self_lifetime.is_none() {
+                    self_lifetime = Some(lifetime.clone());
+                }
+                input_lifetimes.push(lifetime);
+            }
+            if !region_edits.is_empty() {
+                let before = &signature[region.start..region.end];
+                let local: Vec<_> = region_edits.iter().map(|(s, e, t)| (s - region.start, e - region.start, t.clone())).collect();
+                steps.push(format!("rule 1: `{}` becomes `{}`", before, apply(before, local)));
+            }
+            edits.extend(region_edits);
+        }
+
+        let elided_outputs = output.iter().flat_map(|r| &r.slots).filter(|s| !matches!(s, Slot::Named(_))).count();
+        if elided_outputs > 0 {
+            let chosen = if let Some(lifetime) = &self_lifetime {
+                steps.push(format!("rule 3: self is borrowed for {}, so every elided output gets {}", lifetime, lifetime));
+                lifetime.clone()
+            } else if input_lifetimes.len() == 1 {
+                steps.push(format!("rule 2: {} is the only input lifetime, so every elided output gets {}",
+                                   input_lifetimes[0], input_lifetimes[0]));
+                input_lifetimes[0].clone()
+            } else {
+                let reason = if input_lifetimes.is_empty() {
+                    "the return type borrows but there are no input lifetimes to borrow from, rule 2 needs exactly one".to_string()
+                } else {
+                    format!("the return type borrows but there are {} input lifetimes ({}) and none of them is &self, \
+                             neither rule 2 nor rule 3 can pick one", input_lifetimes.len(), input_lifetimes.join(", "))
+                };
+                return Ok(Expanded::Illegal { reason, steps });
+            };
+            edits.extend(output.iter().flat_map(|r| &r.slots).filter_map(|slot| slot.edit(&chosen)));
+        } else if output.is_some() {
+            steps.push("the return type has no elided lifetimes, rules 2 and 3 are not needed".to_string());
+        }
+
+        if !added.is_empty() {
+            let list = added.join(", ");
+            edits.push(match generics {
+                Some((at, true)) => (at, at, list),
+                Some((at, false)) => (at, at, format!("{}, ", list)),
+                None => (name.end(), name.end(), format!("<{}>", list)),
+            });
+        }
+        if steps.is_empty() {
+            steps.push("nothing was elided".to_string());
+        }
+        Ok(Expanded::Legal { signature: apply(signature, edits), steps })
+    }
+
+    fn region(&self, tokens: &[Token]) -> Region {
+        let mut slots = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = &tokens[i];
+            let next = tokens.get(i + 1);
+            match token.text {
+                "&" => {
+                    if let Some(lifetime) = next.filter(|t| t.is_lifetime()) {
+                        slots.push(named_or_placeholder(lifetime));
+                        i += 1;
+                    } else {
+                        slots.push(Slot::Reference { at: token.end() });
+                    }
+                }
+                "fn" | "Fn" | "FnMut" | "FnOnce" if next.is_some_and(|t| t.text == "(") => {
+                    // skip the nested signature, it elides on its own
+                    i = matching(tokens, i + 1, "(", ")").unwrap_or(tokens.len());
+                    if tokens.get(i + 1).is_some_and(|t| t.text == "->") {
+                        i += 2;
+                        while i + 1 < tokens.len() && !matches!(tokens[i + 1].text, "," | "+" | ">" | ")") {
+                            i += 1;
+                        }
+                    }
+                }
+                _ if token.is_lifetime() => slots.push(named_or_placeholder(token)),
+                _ if self.lifetime_types.iter().any(|t| t == token.text)
+                    && next.is_none_or(|t| t.text != "<") => slots.push(Slot::Path { at: token.end() }),
+                _ => {}
+            }
+            i += 1;
+        }
+        let is_self_ref = tokens.iter().any(|t| t.text == "self") && tokens.first().is_some_and(|t| t.text == "&")
+            || tokens.len() > 2 && tokens[0].text == "self" && tokens[1].text == ":" && tokens[2].text == "&";
+        Region {
+            start: tokens.first().map_or(0, |t| t.start),
+            end: tokens.last().map_or(0, |t| t.end()),
+            slots,
+            is_self_ref,
+        }
+    }
+}
+
+fn named_or_placeholder(lifetime: &Token) -> Slot {
+    if lifetime.text == "'_" {
+        Slot::Placeholder { start: lifetime.start, end: lifetime.end() }
+    } else {
+        Slot::Named(lifetime.text.to_string())
+    }
+}
+
+/// Index of the token closing the group opened at tokens[open].
+fn matching(tokens: &[Token], open: usize, left: &str, right: &str) -> Result<usize, String> {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.text == left {
+            depth += 1;
+        } else if token.text == right {
+            depth -= 1;
+            if depth == 0 {
+                return Ok(i);
+            }
+        }
+    }
+    Err(format!("unbalanced {}{}", left, right))
+}
+
+/// Bracket nesting at the end of the tokens, so commas inside `HashMap<K, V>` do not split parameters.
+fn depth(tokens: &[Token]) -> i32 {
+    tokens.iter()
+          .map(|t| match t.text {
+              "<" | "(" | "[" => 1,
+              ">" | ")" | "]" => -1,
+              _ => 0,
+          })
+          .sum()
+}
+
+/// 'a, 'b, ... 'z, 'a1, ... skipping anything already declared.
+fn names(declared: &[String]) -> impl Iterator<Item = String> + '_ {
+    (0..).map(|n: usize| {
+             let letter = (b'a' + (n % 26) as u8) as char;
+             match n / 26 {
+                 0 => format!("'{}", letter),
+                 round => format!("'{}{}", letter, round),
+             }
+         })
+         .filter(move |name| !declared.contains(name))
+}
+
+fn apply(text: &str, mut edits: Vec<(usize, usize, String)>) -> String {
+    edits.sort_by_key(|(start, _, _)| std::cmp::Reverse(*start));
+    let mut result = text.to_string();
+    for (start, end, replacement) in edits {
+        result.replace_range(start..end, &replacement);
+    }
+    result
+}
+
+/// Just enough of the types named in the Rustonomicon table for each line to compile
+const TABLE_PRELUDE: &str = "\
+trait ToCStr {}
+struct T;
+struct Command;
+struct BufWriter<'a>(&'a mut [u8]);
+";
+
+/// Each form implements a trait declared with the other. rustc rejects an impl whose
+/// signature does not match, so passing both ways means the two forms are the same.
+fn equivalence_program(elided: &str, expanded: &str) -> String {
+    let body = |signature: &str| format!("{} {{ loop {{}} }}", signature.trim_end_matches(';'));
+    format!("{}trait Elided {{ {} }}\nimpl Elided for Command {{ {} }}\n\
+             trait Expanded {{ {} }}\nimpl Expanded for Command {{ {} }}\n",
+            TABLE_PRELUDE, elided, body(expanded), expanded, body(elided))
+}
+
+/// Checks one pair of the Rustonomicon table: the expander must agree with it and rustc must
+/// accept the two forms as the same signature, or reject an ILLEGAL one with E0106.
+pub(crate) fn verify_pair(pair: &ElisionPair) -> Result<(), String> {
+    let expander = Expander::new().with_lifetime_type("BufWriter");
+    match (expander.expand(pair.elided)?, pair.expanded) {
+        (Expanded::Legal { signature, .. }, Some(expanded)) => {
+            if signature != expanded {
+                return Err(format!("the expander wrote {}", signature));
+            }
+            let compiled = rustc::check_lib(&equivalence_program(pair.elided, expanded))?;
+            if !compiled.success {
+                return Err(format!("rustc does not treat the two forms as the same:\n{}", compiled.stderr));
+            }
+        }
+        (Expanded::Illegal { .. }, None) => {
+            let compiled = rustc::check_lib(&format!("{}trait Elided {{ {} }}\n", TABLE_PRELUDE, pair.elided))?;
+            if compiled.error_codes() != ["E0106"] {
+                return Err(format!("expected E0106 but rustc reported {:?}", compiled.error_codes()));
+            }
+        }
+        (Expanded::Legal { signature, .. }, None) => return Err(format!("should be ILLEGAL but expanded to {}", signature)),
+        (Expanded::Illegal { reason, .. }, Some(_)) => return Err(format!("should expand but was ILLEGAL: {}", reason)),
+    }
+    Ok(())
+}
+
+pub(crate) fn verify_table() -> Result<(), String> {
+    let mut failed = 0;
+    for pair in RUSTONOMICON_ELISION {
+        match verify_pair(pair) {
+            Ok(()) => println!("ok     {}", pair.elided),
+            Err(message) => {
+                failed += 1;
+                println!("FAILED {}: {}", pair.elided, message);
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} of {} elision pairs did not hold", n, RUSTONOMICON_ELISION.len())),
+    }
+}
+
+pub(crate) fn run(signature: &str, lifetime_types: &[String]) -> Result<(), String> {
+    let expander = lifetime_types.iter().fold(Expander::new(), |e, t| e.with_lifetime_type(t));
+    match expander.expand(signature)? {
+        Expanded::Legal { signature: expanded, steps } => {
+            println!("{}", signature.trim());
+            println!("{}", expanded.trim());
+            steps.iter().for_each(|step| println!("  {}", step));
+        }
+        Expanded::Illegal { reason, steps } => {
+            println!("{}", signature.trim());
+            println!("ILLEGAL: {}", reason);
+            steps.iter().for_each(|step| println!("  {}", step));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legal(expander: &Expander, signature: &str) -> String {
+        match expander.expand(signature).unwrap() {
+            Expanded::Legal { signature, .. } => signature,
+            Expanded::Illegal { reason, .. } => panic!("{} was illegal: {}", signature, reason),
+        }
+    }
+
+    #[test]
+    fn test_expands_lesson_examples() {
+        let e = Expander::new();
+        assert_eq!("fn print<'a>(s: &'a str);", legal(&e, "fn print(s: &str);"));
+        assert_eq!("fn substr<'a>(s: &'a str, until: usize) -> &'a str;",
+                   legal(&e, "fn substr(s: &str, until: usize) -> &str;"));
+        assert_eq!("fn args<'a, 'b, T: ToCStr>(&'a mut self, args: &'b [T]) -> &'a mut Command",
+                   legal(&e, "fn args<T: ToCStr>(&mut self, args: &[T]) -> &mut Command"));
+        assert_eq!("fn do_something1<'a>(_x: String, y: &'a String) -> &'a String",
+                   legal(&e, "fn do_something1(_x: String, y: &String) -> &String"));
+    }
+
+    #[test]
+    fn test_placeholders_and_hidden_lifetimes() {
+        let e = Expander::new().with_lifetime_type("BufWriter");
+        assert_eq!("fn new<'a>(buf: &'a mut [u8]) -> BufWriter<'a>;", legal(&e, "fn new(buf: &mut [u8]) -> BufWriter;"));
+        assert_eq!("fn new<'a>(buf: &'a mut [u8]) -> BufWriter<'a>;", legal(&e, "fn new(buf: &mut [u8]) -> BufWriter<'_>;"));
+    }
+
+    #[test]
+    fn test_named_lifetimes_are_kept() {
+        let e = Expander::new();
+        assert_eq!("fn pick<'b, 'a>(x: &'a str, y: &'b str) -> &'a str",
+                   legal(&e, "fn pick<'a>(x: &'a str, y: &str) -> &'a str"));
+        assert_eq!("fn process_a<'a>(self: &'a Self) -> &'a String",
+                   legal(&e, "fn process_a(self: &Self) -> &String"));
+        assert_eq!("fn apply<'a, F>(text: &'a str, f: fn(&str) -> &str) -> &'a str",
+                   legal(&e, "fn apply<F>(text: &str, f: fn(&str) -> &str) -> &str"));
+    }
+
+    #[test]
+    fn test_illegal_signatures() {
+        let e = Expander::new();
+        for signature in ["fn get_str() -> &str;", "fn frob(s: &str, t: &str) -> &str;"] {
+            match e.expand(signature).unwrap() {
+                Expanded::Illegal { reason, .. } => assert!(reason.contains("rule 2"), "{}", reason),
+                Expanded::Legal { signature, .. } => panic!("expected ILLEGAL but got {}", signature),
+            }
+        }
+        assert!(e.expand("struct Nope").is_err());
+    }
+
+    #[test]
+    fn test_rustonomicon_table() {
+        for pair in RUSTONOMICON_ELISION {
+            if let Err(message) = verify_pair(pair) {
+                panic!("{}: {}", pair.elided, message);
+            }
+        }
+    }
+
+    #[test]
+    fn test_equivalence_catches_a_wrong_expansion() {
+        let program = equivalence_program("fn substr(s: &str, until: usize) -> &str;",
+                                          "fn substr<'a>(s: &'a str, until: usize) -> &'static str;");
+        assert!(!rustc::check_lib(&program).unwrap().success);
+    }
+}
@@ -0,0 +1,215 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::rustc::{self, Scratch};
+
+/// One function for the learner to write, graded by the hidden test of the same name.
+pub(crate) struct Exercise {
+    pub(crate) name: &'static str,
+    pub(crate) summary: &'static str,
+}
+
+/// The stubs a learner starts from and the tests their answers are graded with.
+pub(crate) struct Workbook {
+    pub(crate) lesson: u32,
+    pub(crate) stubs: &'static str,
+    /// #[test] functions named after the exercises, compiled in a module below the learner's code
+    pub(crate) tests: &'static str,
+    pub(crate) exercises: &'static [Exercise],
+}
+
+pub(crate) static WORKBOOKS: &[Workbook] = &[
+    Workbook {
+        lesson: 1,
+        stubs: include_str!("../exercises/stubs/lesson_1.rs"),
+        tests: include_str!("../exercises/tests/lesson_1.rs"),
+        exercises: &[
+            Exercise { name: "first_word", summary: "return a slice of the input with an elided lifetime" },
+            Exercise { name: "append_world", summary: "mutate through &mut and return a shared reference" },
+        ],
+    },
+    Workbook {
+        lesson: 2,
+        stubs: include_str!("../exercises/stubs/lesson_2.rs"),
+        tests: include_str!("../exercises/tests/lesson_2.rs"),
+        exercises: &[
+            Exercise { name: "update_b", summary: "store a reference with the struct's lifetime" },
+            Exercise { name: "consume_return_b", summary: "hand back 'b from a struct with split lifetimes" },
+        ],
+    },
+    Workbook {
+        lesson: 3,
+        stubs: include_str!("../exercises/stubs/lesson_3.rs"),
+        tests: include_str!("../exercises/tests/lesson_3.rs"),
+        exercises: &[
+            Exercise { name: "shortest_length", summary: "return the shortest string using lifetime subtyping" },
+            Exercise { name: "hold_and_ref", summary: "move a value into a struct and return a reference to it" },
+            Exercise { name: "apply_to_str", summary: "call a closure with a higher-rank trait bound" },
+        ],
+    },
+];
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Grade {
+    Pass,
+    /// Still the todo!() from the stub
+    Todo,
+    Fail(String),
+}
+
+pub(crate) enum Report {
+    Graded(Vec<Grade>),
+    /// rustc's diagnostics for the learner's file
+    DoesNotCompile(String),
+}
+
+pub(crate) fn workbook(lesson: u32) -> Result<&'static Workbook, String> {
+    WORKBOOKS.iter()
+             .find(|w| w.lesson == lesson)
+             .ok_or_else(|| format!("lesson {} has no exercises", lesson))
+}
+
+/// Where the learner's answers live unless --file says otherwise. It is ignored by
+/// git and never the stub file itself, so the stubs stay clean for the next learner.
+pub(crate) fn default_file(lesson: u32) -> PathBuf {
+    PathBuf::from(format!("exercises/workspace/lesson_{}.rs", lesson))
+}
+
+/// Compiles the learner's code with the hidden tests and runs each exercise's test
+/// on its own, or returns rustc's diagnostics when the code does not compile.
+pub(crate) fn grade(workbook: &Workbook, answers: &str) -> Result<Report, String> {
+    let source = format!("{}\n#[cfg(test)]\nmod hidden {{\n{}\n}}\n", answers, workbook.tests);
+    let scratch = Scratch::new().map_err(|e| format!("unable to create temp dir: {}", e))?;
+    let compiled = rustc::build_tests(&source, &scratch)?;
+    if !compiled.success {
+        return Ok(Report::DoesNotCompile(compiled.stderr));
+    }
+    let mut grades = Vec::new();
+    for exercise in workbook.exercises {
+        let output = Command::new(rustc::test_runner(&scratch))
+            .args(["--exact", &format!("hidden::{}", exercise.name), "--test-threads=1"])
+            .output()
+            .map_err(|e| format!("unable to run the tests: {}", e))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        grades.push(if output.status.success() && stdout.contains("1 passed") {
+            Grade::Pass
+        } else if stdout.contains("not yet implemented") {
+            Grade::Todo
+        } else {
+            // the line after "panicked at" holds the assertion message
+            let message = stdout.lines()
+                                .skip_while(|line| !line.contains("panicked at"))
+                                .nth(1)
+                                .unwrap_or("the test did not pass");
+            Grade::Fail(message.trim().to_string())
+        });
+    }
+    Ok(Report::Graded(grades))
+}
+
+pub(crate) fn check(lesson: u32, file: &Path) -> Result<(), String> {
+    let workbook = workbook(lesson)?;
+    let answers = match fs::read_to_string(file) {
+        Ok(answers) => answers,
+        // a fresh workspace, hand the learner the stubs to start from
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if let Some(dir) = file.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir).map_err(|e| format!("unable to create {}: {}", dir.display(), e))?;
+            }
+            fs::write(file, workbook.stubs).map_err(|e| format!("unable to write {}: {}", file.display(), e))?;
+            println!("wrote the lesson {} stubs to {}, replace each todo!() and check again", lesson, file.display());
+            workbook.stubs.to_string()
+        }
+        Err(e) => return Err(format!("unable to read {}: {}", file.display(), e)),
+    };
+    println!("checking lesson {} exercises in {}", lesson, file.display());
+    let grades = match grade(workbook, &answers)? {
+        Report::Graded(grades) => grades,
+        Report::DoesNotCompile(diagnostics) => {
+            println!("{}", diagnostics.trim_end());
+            return Err(format!("{} does not compile", file.display()));
+        }
+    };
+    let width = workbook.exercises.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    for (exercise, grade) in workbook.exercises.iter().zip(&grades) {
+        match grade {
+            Grade::Pass => println!("  pass   {:<width$}  {}", exercise.name, exercise.summary),
+            Grade::Todo => println!("  todo   {:<width$}  {}", exercise.name, exercise.summary),
+            Grade::Fail(message) => println!("  FAILED {:<width$}  {}", exercise.name, message),
+        }
+    }
+    match grades.iter().filter(|g| **g != Grade::Pass).count() {
+        0 => {
+            println!("all {} exercises pass", grades.len());
+            Ok(())
+        }
+        n => Err(format!("{} of {} exercises do not pass yet", n, grades.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::lessons;
+
+    fn graded(workbook: &Workbook, answers: &str) -> Vec<Grade> {
+        match grade(workbook, answers).unwrap() {
+            Report::Graded(grades) => grades,
+            Report::DoesNotCompile(diagnostics) => panic!("lesson {}: {}", workbook.lesson, diagnostics),
+        }
+    }
+
+    fn solution(lesson: u32) -> &'static str {
+        match lesson {
+            1 => include_str!("../exercises/solutions/lesson_1.rs"),
+            2 => include_str!("../exercises/solutions/lesson_2.rs"),
+            3 => include_str!("../exercises/solutions/lesson_3.rs"),
+            _ => panic!("no solution for lesson {}", lesson),
+        }
+    }
+
+    #[test]
+    fn test_every_exercise_has_a_test() {
+        for workbook in WORKBOOKS {
+            assert!(lessons().iter().any(|l| l.id() == workbook.lesson));
+            for exercise in workbook.exercises {
+                assert!(workbook.tests.contains(&format!("fn {}()", exercise.name)), "{}", exercise.name);
+                assert!(workbook.stubs.contains(&format!("fn {}", exercise.name)), "{}", exercise.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stubs_are_todo_and_solutions_pass() {
+        for workbook in WORKBOOKS {
+            let stubs = graded(workbook, workbook.stubs);
+            assert!(stubs.iter().all(|g| *g == Grade::Todo), "lesson {} stubs: {:?}", workbook.lesson, stubs);
+            let solved = graded(workbook, solution(workbook.lesson));
+            assert!(solved.iter().all(|g| *g == Grade::Pass), "lesson {} solution: {:?}", workbook.lesson, solved);
+        }
+    }
+
+    #[test]
+    fn test_workspace_is_not_the_stubs() {
+        let ignored = include_str!("../.gitignore");
+        for workbook in WORKBOOKS {
+            let file = default_file(workbook.lesson);
+            assert!(file.starts_with("exercises/workspace"), "{}", file.display());
+            assert!(!file.starts_with("exercises/stubs"), "{}", file.display());
+        }
+        assert!(ignored.lines().any(|line| line == "/exercises/workspace/"), "the workspace must be gitignored");
+    }
+
+    #[test]
+    fn test_wrong_answers_are_reported() {
+        let workbook = workbook(1).unwrap();
+        let wrong = solution(1).replace("text.split(' ')", "text.split(',')");
+        let grades = graded(workbook, &wrong);
+        assert!(matches!(grades[0], Grade::Fail(_)), "{:?}", grades);
+        assert_eq!(Grade::Pass, grades[1]);
+
+        let broken = solution(1).replace("-> &String", "-> &'static String");
+        assert!(matches!(grade(workbook, &broken).unwrap(), Report::DoesNotCompile(_)));
+    }
+}
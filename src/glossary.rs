@@ -0,0 +1,470 @@
+use crate::lesson::lessons;
+
+/// A term from the Vocabulary blocks. The definition is the one written in the
+/// header of the lesson that introduces the term, the tests keep the two the same.
+pub(crate) struct Entry {
+    pub(crate) term: &'static str,
+    pub(crate) definition: &'static str,
+    pub(crate) pronunciation: Option<&'static str>,
+    /// The lesson that introduces the term
+    pub(crate) lesson: u32,
+    pub(crate) see_also: &'static [&'static str],
+}
+
+pub(crate) static GLOSSARY: &[Entry] = &[
+    Entry {
+        term: "Borrowing",
+        definition: "Accessing a value through a reference without taking ownership of it.",
+        pronunciation: None,
+        lesson: 1,
+        see_also: &["Immutable Reference", "Mutable Reference", "Ownership"],
+    },
+    Entry {
+        term: "Immutable Reference",
+        definition: "A reference to a value that cannot be changed. Multiple immutable references can exist at the same time as long as there are no mutable.",
+        pronunciation: None,
+        lesson: 1,
+        see_also: &["Mutable Reference", "Borrowing"],
+    },
+    Entry {
+        term: "Mutable Reference",
+        definition: "A reference that allows the underlying value to be changed. Only one mutable reference to a particular value can exist at a time to prevent data races. This is enforced by Rust's borrow checker.",
+        pronunciation: None,
+        lesson: 1,
+        see_also: &["Immutable Reference", "Borrowing"],
+    },
+    Entry {
+        term: "Lifetime",
+        definition: "A named region of code during which a REFERENCE is valid. This is often aligned with the scope defined by { } but could be smaller based on last usage. It could even be larger than a scope by naming the lifetime and defining it outside the scope.",
+        pronunciation: None,
+        lesson: 1,
+        see_also: &["Elision", "Lifetime Subtyping"],
+    },
+    Entry {
+        term: "Elision",
+        definition: "The compiler's ability to infer lifetimes based on the structure of the code. Similar to the english contraction \"I'm\" vs \"I Am\"",
+        pronunciation: Some("ih-LIZH-uhn, ih as in kit, LIZH as in collision, uhn as in button"),
+        lesson: 1,
+        see_also: &["Elided", "Lifetime Elision"],
+    },
+    Entry {
+        term: "Elided",
+        definition: "The act of removing or omitting something.",
+        pronunciation: Some("ih-LY-did, ih as in kit, LY as in fly, did as in did"),
+        lesson: 1,
+        see_also: &["Elision"],
+    },
+    Entry {
+        term: "Struct",
+        definition: "A data structure that groups together values of different typed fields under a name.",
+        pronunciation: None,
+        lesson: 2,
+        see_also: &["Implementation", "Method"],
+    },
+    Entry {
+        term: "Implementation",
+        definition: "The block of code that defines the methods of a struct.",
+        pronunciation: None,
+        lesson: 2,
+        see_also: &["Struct", "Method"],
+    },
+    Entry {
+        term: "Method",
+        definition: "A function that is defined within the context of a struct. (Think OO programming)",
+        pronunciation: None,
+        lesson: 2,
+        see_also: &["Implementation"],
+    },
+    Entry {
+        term: "Ownership",
+        definition: "The concept of a single value having a single owner that is responsible for cleaning up the value when it goes out of scope.",
+        pronunciation: None,
+        lesson: 2,
+        see_also: &["Drop", "Borrowing"],
+    },
+    Entry {
+        term: "Drop",
+        definition: "The process of cleaning up a value when it goes out of scope.",
+        pronunciation: None,
+        lesson: 2,
+        see_also: &["Ownership"],
+    },
+    Entry {
+        term: "Partial Move",
+        definition: "Moving some fields out of a struct, leaving the rest of it usable.",
+        pronunciation: None,
+        lesson: 2,
+        see_also: &["Destructuring", "Ownership"],
+    },
+    Entry {
+        term: "Destructuring",
+        definition: "Breaking a struct into its fields with a pattern such as let SplitStruct {a, b} = d.",
+        pronunciation: None,
+        lesson: 2,
+        see_also: &["Partial Move"],
+    },
+    Entry {
+        term: "Lifetime Elision",
+        definition: "The process by which the Rust compiler automatically infers lifetimes in function signatures, reducing the need for explicit annotations.",
+        pronunciation: None,
+        lesson: 3,
+        see_also: &["Elision"],
+    },
+    Entry {
+        term: "Lifetime Subtyping",
+        definition: "A concept where one lifetime is a subtype of another, meaning one lifetime can be shorter than another while still satisfying the requirements of the code.",
+        pronunciation: None,
+        lesson: 3,
+        see_also: &["Lifetime"],
+    },
+    Entry {
+        term: "Higher-Rank Trait Bounds (HRTBs)",
+        definition: "A way to express that a function must work for all possible lifetimes, making your code more flexible.",
+        pronunciation: None,
+        lesson: 3,
+        see_also: &["Lifetime"],
+    },
+    Entry {
+        term: "Iterator",
+        definition: "A value with a next() method that yields items one at a time until it returns None.",
+        pronunciation: None,
+        lesson: 4,
+        see_also: &["Iterator Adaptor", "Borrowing Iterator", "Consuming Iterator"],
+    },
+    Entry {
+        term: "Iterator Adaptor",
+        definition: "A method such as map, filter or zip that wraps one iterator in another. The adaptor holds the inner iterator and any borrow it carries.",
+        pronunciation: None,
+        lesson: 4,
+        see_also: &["Iterator"],
+    },
+    Entry {
+        term: "Borrowing Iterator",
+        definition: "The iterator from iter() or iter_mut(), it yields references and the collection is still ours when it is done.",
+        pronunciation: None,
+        lesson: 4,
+        see_also: &["Consuming Iterator", "Borrowing"],
+    },
+    Entry {
+        term: "Consuming Iterator",
+        definition: "The iterator from into_iter() on an owned collection, it yields the items by value and the collection is moved into it.",
+        pronunciation: None,
+        lesson: 4,
+        see_also: &["Borrowing Iterator", "Ownership"],
+    },
+    Entry {
+        term: "impl Trait",
+        definition: "A return type that names a trait instead of the concrete type, the caller only knows it is \"some Iterator\", including which lifetimes it may hold.",
+        pronunciation: None,
+        lesson: 4,
+        see_also: &["Iterator", "Lifetime"],
+    },
+    Entry {
+        term: "Trait Object",
+        definition: "A value used through a pointer like Box<dyn Trait> or &dyn Trait, where only the trait is known and the concrete type is decided at runtime.",
+        pronunciation: None,
+        lesson: 5,
+        see_also: &["Object Lifetime Bound", "Boxed Closure"],
+    },
+    Entry {
+        term: "Object Lifetime Bound",
+        definition: "The + 'a in dyn Trait + 'a, how long anything the hidden value borrows must stay valid.",
+        pronunciation: None,
+        lesson: 5,
+        see_also: &["Default Object Lifetime Bound", "Lifetime"],
+    },
+    Entry {
+        term: "Default Object Lifetime Bound",
+        definition: "The bound the compiler picks when none is written in a signature or field, 'static inside a Box and 'a inside &'a.",
+        pronunciation: None,
+        lesson: 5,
+        see_also: &["Object Lifetime Bound", "Lifetime Elision"],
+    },
+    Entry {
+        term: "Boxed Closure",
+        definition: "A closure stored as Box<dyn Fn(..)>, so closures with different captures can share one field or Vec.",
+        pronunciation: None,
+        lesson: 5,
+        see_also: &["Trait Object"],
+    },
+    Entry {
+        term: "Variance",
+        definition: "How a type's lifetime parameter may change when the type is passed around, it can be covariant, invariant or contravariant.",
+        pronunciation: None,
+        lesson: 6,
+        see_also: &["Covariant", "Invariant", "Contravariant", "Lifetime Subtyping"],
+    },
+    Entry {
+        term: "Covariant",
+        definition: "The lifetime may be shortened, a &'static str can be used as a &'a str.",
+        pronunciation: None,
+        lesson: 6,
+        see_also: &["Variance", "Invariant"],
+    },
+    Entry {
+        term: "Invariant",
+        definition: "The lifetime must match exactly, neither longer nor shorter, as behind &mut T or Cell<T>.",
+        pronunciation: None,
+        lesson: 6,
+        see_also: &["Variance", "Covariant"],
+    },
+    Entry {
+        term: "Contravariant",
+        definition: "The lifetime may be lengthened, only seen in function arguments such as fn(&'a T).",
+        pronunciation: None,
+        lesson: 6,
+        see_also: &["Variance", "Covariant"],
+    },
+    Entry {
+        term: "PhantomData",
+        definition: "A zero sized field that tells the compiler the struct acts as if it held a value of the given type, lifetimes and variance included.",
+        pronunciation: None,
+        lesson: 6,
+        see_also: &["Variance"],
+    },
+    Entry {
+        term: "Closure",
+        definition: "An anonymous function written |args| body that can use variables from the scope it is written in.",
+        pronunciation: None,
+        lesson: 7,
+        see_also: &["Capture", "Move Closure", "FnMut"],
+    },
+    Entry {
+        term: "Capture",
+        definition: "A variable from the surrounding scope that a closure uses, held by shared reference, mutable reference or by value.",
+        pronunciation: None,
+        lesson: 7,
+        see_also: &["Closure", "Disjoint Capture", "Borrowing"],
+    },
+    Entry {
+        term: "Disjoint Capture",
+        definition: "Since edition 2021 a closure captures only the fields it uses, player.score instead of all of player.",
+        pronunciation: None,
+        lesson: 7,
+        see_also: &["Capture", "Partial Move"],
+    },
+    Entry {
+        term: "Move Closure",
+        definition: "A closure written move |args| body, it takes ownership of every capture.",
+        pronunciation: None,
+        lesson: 7,
+        see_also: &["Capture", "Ownership"],
+    },
+    Entry {
+        term: "FnMut",
+        definition: "The trait for closures that change what they capture, calling one needs the closure to be mutable.",
+        pronunciation: None,
+        lesson: 7,
+        see_also: &["Closure", "Mutable Reference"],
+    },
+    Entry {
+        term: "Thread",
+        definition: "An independent path of execution, started with thread::spawn and waited for with join().",
+        pronunciation: None,
+        lesson: 8,
+        see_also: &["Scoped Thread", "Arc"],
+    },
+    Entry {
+        term: "'static Bound",
+        definition: "A requirement written T: 'static, the value must not borrow anything that could go away, owned data and &'static references qualify.",
+        pronunciation: None,
+        lesson: 8,
+        see_also: &["Lifetime", "Ownership", "Thread"],
+    },
+    Entry {
+        term: "Scoped Thread",
+        definition: "A thread spawned inside thread::scope, it is joined before scope returns so it may borrow from the enclosing function.",
+        pronunciation: None,
+        lesson: 8,
+        see_also: &["Thread", "Borrowing"],
+    },
+    Entry {
+        term: "Arc",
+        definition: "An atomically reference counted pointer, each clone owns a share of the value and the last one dropped frees it.",
+        pronunciation: None,
+        lesson: 8,
+        see_also: &["Ownership", "Thread"],
+    },
+    Entry {
+        term: "Self-Referential Struct",
+        definition: "A struct with a field that points into another field of the same struct.",
+        pronunciation: None,
+        lesson: 9,
+        see_also: &["Pin", "Raw Pointer", "Borrowing"],
+    },
+    Entry {
+        term: "Raw Pointer",
+        definition: "A *const T or *mut T, a pointer the borrow checker does not track. Reading through one needs unsafe and the programmer's promise that it is valid.",
+        pronunciation: None,
+        lesson: 9,
+        see_also: &["Pin", "PhantomData"],
+    },
+    Entry {
+        term: "Pin",
+        definition: "A wrapper like Pin<Box<T>> that promises the value behind it will not be moved again, so pointers into it stay valid.",
+        pronunciation: None,
+        lesson: 9,
+        see_also: &["PhantomPinned", "Self-Referential Struct"],
+    },
+    Entry {
+        term: "PhantomPinned",
+        definition: "A marker field that makes a struct !Unpin, once pinned it can not be moved out of its Pin.",
+        pronunciation: None,
+        lesson: 9,
+        see_also: &["Pin", "PhantomData"],
+    },
+];
+
+/// Exact match on the term, ignoring case.
+pub(crate) fn lookup(term: &str) -> Option<&'static Entry> {
+    GLOSSARY.iter().find(|entry| entry.term.eq_ignore_ascii_case(term))
+}
+
+/// The exact term when there is one, otherwise every term containing the text.
+pub(crate) fn search(text: &str) -> Vec<&'static Entry> {
+    if let Some(entry) = lookup(text) {
+        return vec![entry];
+    }
+    let text = text.to_lowercase();
+    GLOSSARY.iter().filter(|entry| entry.term.to_lowercase().contains(&text)).collect()
+}
+
+fn print_entry(entry: &Entry) {
+    println!("{}", entry.term);
+    println!("  {}", entry.definition);
+    if let Some(pronunciation) = entry.pronunciation {
+        println!("  pronounced: {}", pronunciation);
+    }
+    match lessons().iter().find(|lesson| lesson.id() == entry.lesson) {
+        Some(lesson) => println!("  first used: lesson {} {}", lesson.id(), lesson.title()),
+        None => println!("  first used: lesson {}", entry.lesson),
+    }
+    if !entry.see_also.is_empty() {
+        println!("  see also: {}", entry.see_also.join(", "));
+    }
+}
+
+/// Lists every term, or shows the full entry for the ones matching `term`.
+pub(crate) fn run(term: Option<&str>) -> Result<(), String> {
+    let Some(term) = term else {
+        for entry in GLOSSARY {
+            println!("  {:<34} lesson {}", entry.term, entry.lesson);
+        }
+        return Ok(());
+    };
+    let found = search(term);
+    if found.is_empty() {
+        return Err(format!("'{}' is not in the glossary, run glossary with no term to list them", term));
+    }
+    for (i, entry) in found.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_entry(entry);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A term as the lesson header writes it, definition lines joined into one.
+    struct HeaderEntry<'s> {
+        term: &'s str,
+        definition: String,
+        pronunciation: Option<&'s str>,
+    }
+
+    /// The `/// Term: definition` entries under a lesson's Vocabulary banner. Lines indented
+    /// past the `///` continue the definition until a blank line or the "It is pronounced"
+    /// note, which gives the pronunciation instead.
+    fn header_entries(source: &str) -> Vec<HeaderEntry<'_>> {
+        let mut entries: Vec<HeaderEntry> = Vec::new();
+        let mut open = false;
+        let lines = source.lines()
+                          .skip_while(|line| !line.contains("Vocabulary"))
+                          .skip(1)
+                          .take_while(|line| !line.starts_with("////"));
+        for line in lines {
+            let text = line.strip_prefix("///").unwrap_or("");
+            let trimmed = text.trim();
+            if let Some(said) = trimmed.strip_prefix("It is pronounced as \"") {
+                if let Some(entry) = entries.last_mut() {
+                    entry.pronunciation = said.split('"').next();
+                }
+                open = false;
+            } else if trimmed.is_empty() {
+                open = false;
+            } else if !text.starts_with("  ") {
+                if let Some((term, definition)) = trimmed.split_once(':') {
+                    entries.push(HeaderEntry { term: term.trim(), definition: definition.trim().to_string(), pronunciation: None });
+                    open = true;
+                }
+            } else if open {
+                if let Some(entry) = entries.last_mut() {
+                    entry.definition.push(' ');
+                    entry.definition.push_str(trimmed);
+                }
+            }
+        }
+        for entry in &mut entries {
+            entry.definition = entry.definition.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+        entries
+    }
+
+    #[test]
+    fn test_every_vocabulary_term_is_defined() {
+        for lesson in lessons() {
+            // the header is what the learner reads, vocabulary() has to list the same terms
+            let header = header_entries(lesson.source());
+            let terms: Vec<&str> = header.iter().map(|entry| entry.term).collect();
+            assert!(!terms.is_empty(), "lesson {} has no vocabulary header", lesson.id());
+            assert_eq!(terms, lesson.vocabulary(), "lesson {} header and vocabulary() differ", lesson.id());
+            for written in header {
+                let term = written.term;
+                let entry = lookup(term).unwrap_or_else(|| panic!("lesson {} uses '{}' which is not in the glossary",
+                                                                  lesson.id(), term));
+                assert!(entry.lesson <= lesson.id(), "'{}' is used in lesson {} before it is introduced", term, lesson.id());
+                // the lesson that introduces a term writes it out in full, later ones may restate the start of it
+                if entry.lesson == lesson.id() {
+                    assert_eq!(written.definition, entry.definition, "lesson {} defines '{}' differently", lesson.id(), term);
+                } else {
+                    assert!(entry.definition.starts_with(&written.definition),
+                            "lesson {} restates '{}' differently: {}", lesson.id(), term, written.definition);
+                }
+                if let Some(said) = written.pronunciation {
+                    assert!(entry.pronunciation.is_some_and(|p| p.starts_with(said)), "'{}' is pronounced {}", term, said);
+                }
+            }
+        }
+        let header = header_entries("/* Vocabulary */\n/***/\n/// Lifetime Elision: infers\n///                   more: text\n///\n\
+                                     /// Elided: omitted.\n///         It is pronounced as \"ih-LY-did\" where:\n\
+                                     ///                 ih sounds like kit\n////////\n/// Later: c\n");
+        assert_eq!(vec!["Lifetime Elision", "Elided"], header.iter().map(|e| e.term).collect::<Vec<_>>());
+        assert_eq!("infers more: text", header[0].definition);
+        assert_eq!(("omitted.", Some("ih-LY-did")), (header[1].definition.as_str(), header[1].pronunciation));
+    }
+
+    #[test]
+    fn test_entries_are_consistent() {
+        for entry in GLOSSARY {
+            assert_eq!(1, GLOSSARY.iter().filter(|e| e.term == entry.term).count(), "{} twice", entry.term);
+            for other in entry.see_also {
+                assert!(lookup(other).is_some(), "{} refers to missing '{}'", entry.term, other);
+            }
+            let lesson = lessons().iter().find(|l| l.id() == entry.lesson)
+                                  .unwrap_or_else(|| panic!("{} names lesson {}", entry.term, entry.lesson));
+            assert!(lesson.source().to_lowercase().contains(&entry.term.to_lowercase()),
+                    "lesson {} never mentions {}", entry.lesson, entry.term);
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        assert_eq!(vec!["Elision"], search("elision").iter().map(|e| e.term).collect::<Vec<_>>());
+        assert_eq!(2, search("reference").len());
+        assert!(search("monad").is_empty());
+    }
+}
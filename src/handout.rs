@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::Path;
+use crate::capture;
+use crate::glossary;
+use crate::lesson::{Example, Lesson};
+use crate::source;
+
+pub(crate) const HANDOUT_DIR: &str = "handouts";
+
+/// Everything a handout shows for one lesson, pulled from the lesson itself so the
+/// Markdown and HTML can never say something the code does not.
+pub(crate) struct Handout<'l> {
+    pub(crate) lesson: &'l dyn Lesson,
+    pub(crate) prose: Vec<String>,
+    pub(crate) sections: Vec<Section>,
+}
+
+pub(crate) struct Section {
+    pub(crate) example: &'static Example,
+    pub(crate) source: String,
+    pub(crate) output: Vec<String>,
+}
+
+impl<'l> Handout<'l> {
+    /// Output is passed in so tests need not spawn the binary.
+    pub(crate) fn new(lesson: &'l dyn Lesson, examples: &[&'static Example],
+                      output: impl Fn(&Example) -> Result<Vec<String>, String>) -> Result<Handout<'l>, String> {
+        let mut sections = Vec::new();
+        for example in examples {
+            let source = source::example_source(lesson, example)
+                .ok_or_else(|| format!("no source found for example {}.{}", lesson.id(), example.id))?;
+            sections.push(Section { example, source, output: output(example)? });
+        }
+        Ok(Handout { lesson, prose: source::lesson_prose(lesson.source()), sections })
+    }
+
+    fn heading(&self) -> String {
+        format!("Lesson {}: {}", self.lesson.id(), self.lesson.title())
+    }
+
+    pub(crate) fn markdown(&self) -> String {
+        let mut md = format!("# {}\n\n", self.heading());
+        for paragraph in &self.prose {
+            md.push_str(&format!("{}\n\n", paragraph));
+        }
+        md.push_str("## Vocabulary\n\n");
+        for term in self.lesson.vocabulary() {
+            match glossary::lookup(term) {
+                Some(entry) => {
+                    md.push_str(&format!("- **{}**: {}", entry.term, entry.definition));
+                    if let Some(pronunciation) = entry.pronunciation {
+                        md.push_str(&format!(" *Pronounced {}.*", pronunciation));
+                    }
+                    md.push('\n');
+                }
+                None => md.push_str(&format!("- **{}**\n", term)),
+            }
+        }
+        for section in &self.sections {
+            md.push_str(&format!("\n## Example {}: {}\n\n", section.example.id, section.example.title));
+            md.push_str(&format!("```rust\n{}\n```\n\n", section.source));
+            md.push_str("Output:\n\n");
+            md.push_str(&format!("```text\n{}```\n", section.output.iter().map(|l| format!("{}\n", l)).collect::<String>()));
+        }
+        md
+    }
+
+    /// A single file with its styles inline, so it can be mailed or opened offline.
+    pub(crate) fn html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", escape(&self.heading())));
+        html.push_str("<style>\n\
+                       body { font-family: sans-serif; max-width: 50em; margin: 2em auto; line-height: 1.5; }\n\
+                       pre { background: #f4f4f4; padding: 0.75em; overflow-x: auto; }\n\
+                       pre.output { background: #1e1e1e; color: #e0e0e0; }\n\
+                       dt { font-weight: bold; }\n\
+                       .pronounced { font-style: italic; }\n\
+                       </style>\n</head>\n<body>\n");
+        html.push_str(&format!("<h1>{}</h1>\n", escape(&self.heading())));
+        for paragraph in &self.prose {
+            html.push_str(&format!("<p>{}</p>\n", escape(paragraph)));
+        }
+        html.push_str("<h2>Vocabulary</h2>\n<dl>\n");
+        for term in self.lesson.vocabulary() {
+            match glossary::lookup(term) {
+                Some(entry) => {
+                    html.push_str(&format!("<dt>{}</dt>\n<dd>{}", escape(entry.term), escape(entry.definition)));
+                    if let Some(pronunciation) = entry.pronunciation {
+                        html.push_str(&format!(" <span class=\"pronounced\">Pronounced {}.</span>", escape(pronunciation)));
+                    }
+                    html.push_str("</dd>\n");
+                }
+                None => html.push_str(&format!("<dt>{}</dt>\n", escape(term))),
+            }
+        }
+        html.push_str("</dl>\n");
+        for section in &self.sections {
+            html.push_str(&format!("<h2>Example {}: {}</h2>\n", escape(section.example.id), escape(section.example.title)));
+            html.push_str(&format!("<pre><code>{}</code></pre>\n", escape(&section.source)));
+            html.push_str("<p>Output:</p>\n");
+            html.push_str(&format!("<pre class=\"output\">{}</pre>\n", escape(&section.output.join("\n"))));
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes lesson_N.md and lesson_N.html for every lesson with a selected example.
+pub(crate) fn export(selected: &[(&'static dyn Lesson, &'static Example)], dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("unable to create {}: {}", dir.display(), e))?;
+    let mut done: Vec<u32> = Vec::new();
+    for (lesson, _) in selected {
+        if done.contains(&lesson.id()) {
+            continue;
+        }
+        done.push(lesson.id());
+        let examples: Vec<&'static Example> = selected.iter()
+                                                      .filter(|(l, _)| l.id() == lesson.id())
+                                                      .map(|(_, e)| *e)
+                                                      .collect();
+        let handout = Handout::new(*lesson, &examples, |example| capture::example_output(*lesson, example))?;
+        for (extension, text) in [("md", handout.markdown()), ("html", handout.html())] {
+            let path = dir.join(format!("lesson_{}.{}", lesson.id(), extension));
+            fs::write(&path, text).map_err(|e| format!("unable to write {}: {}", path.display(), e))?;
+            println!("wrote {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::lessons;
+
+    fn handout(lesson: &'static dyn Lesson) -> Handout<'static> {
+        let examples: Vec<&'static Example> = lesson.examples().iter().collect();
+        Handout::new(lesson, &examples, |example| Ok(vec![format!("output of {} <&>", example.id)])).unwrap()
+    }
+
+    #[test]
+    fn test_markdown_has_every_part() {
+        let lesson = lessons()[0];
+        let md = handout(lesson).markdown();
+        assert!(md.starts_with("# Lesson 1: Reference Review - Borrowing and scope\n"));
+        assert!(md.contains("Imagine you lend a book to a friend (reference)."), "{}", md);
+        assert!(md.contains("- **Elision**: The compiler's ability"), "{}", md);
+        assert!(md.contains("*Pronounced ih-LIZH-uhn"), "{}", md);
+        for example in lesson.examples() {
+            assert!(md.contains(&format!("## Example {}: {}", example.id, example.title)));
+            assert!(md.contains(&format!("```rust\nfn {}() {{", source::example_fn_name(example))));
+            assert!(md.contains(&format!("output of {} <&>", example.id)));
+        }
+    }
+
+    #[test]
+    fn test_html_is_escaped() {
+        for lesson in lessons() {
+            let html = handout(*lesson).html();
+            assert!(html.starts_with("<!DOCTYPE html>"));
+            assert!(html.ends_with("</html>\n"));
+            assert!(html.contains("output of 1 &lt;&amp;&gt;"));
+            assert!(!html.contains("<'a>"), "lesson {} has an unescaped lifetime", lesson.id());
+        }
+    }
+}
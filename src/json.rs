@@ -0,0 +1,96 @@
+use crate::capture;
+use crate::lesson::{Example, Lesson};
+use crate::source;
+
+/// What one example looks like to anything reading `run --format json`.
+pub(crate) struct Record<'a> {
+    pub(crate) lesson: u32,
+    pub(crate) lesson_title: &'a str,
+    pub(crate) example: &'a str,
+    pub(crate) title: &'a str,
+    pub(crate) source: &'a str,
+    pub(crate) output: Vec<String>,
+}
+
+/// A JSON string literal, quotes included. Control characters become \u escapes.
+pub(crate) fn string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// An array of records, one object per line of fields so the output still diffs well.
+pub(crate) fn render(records: &[Record]) -> String {
+    let objects: Vec<String> = records.iter().map(|record| {
+        let output: Vec<String> = record.output.iter().map(|line| string(line)).collect();
+        format!("  {{\n    \"lesson\": {},\n    \"lesson_title\": {},\n    \"example\": {},\n    \"title\": {},\n    \
+                 \"source\": {},\n    \"output\": [{}]\n  }}",
+                record.lesson, string(record.lesson_title), string(record.example), string(record.title),
+                string(record.source), output.join(", "))
+    }).collect();
+    if objects.is_empty() {
+        return "[]".to_string();
+    }
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+/// Runs each example in its own process to collect its output and prints the lot as JSON.
+pub(crate) fn run(selected: &[(&'static dyn Lesson, &'static Example)]) -> Result<(), String> {
+    let sources: Vec<String> = selected.iter()
+                                       .map(|(lesson, example)| source::example_source(*lesson, example).unwrap_or_default())
+                                       .collect();
+    let mut records = Vec::new();
+    for ((lesson, example), source) in selected.iter().zip(&sources) {
+        records.push(Record {
+            lesson: lesson.id(),
+            lesson_title: lesson.title(),
+            example: example.id,
+            title: example.title,
+            source,
+            output: capture::example_output(*lesson, example)?,
+        });
+    }
+    println!("{}", render(&records));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(r#""plain""#, string("plain"));
+        assert_eq!(r#""say \"hi\"\n\tback\\slash""#, string("say \"hi\"\n\tback\\slash"));
+        assert_eq!(r#""bell \u0007 and é""#, string("bell \u{7} and é"));
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!("[]", render(&[]));
+        let records = [Record {
+            lesson: 2,
+            lesson_title: "Struct and self",
+            example: "3.5",
+            title: "methods over split lifetimes",
+            source: "fn example_3_5() {\n}",
+            output: vec!["a:\"aaa\"".to_string(), String::new()],
+        }];
+        assert_eq!("[\n  {\n    \"lesson\": 2,\n    \"lesson_title\": \"Struct and self\",\n    \"example\": \"3.5\",\n    \
+                    \"title\": \"methods over split lifetimes\",\n    \"source\": \"fn example_3_5() {\\n}\",\n    \
+                    \"output\": [\"a:\\\"aaa\\\"\", \"\"]\n  }\n]",
+                   render(&records));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::cli::{Selection, Selector};
+use crate::glossary;
+use crate::lesson_1_reference::ReferenceLesson;
+use crate::lesson_2_struct::StructLesson;
+use crate::lesson_3_function::FunctionLesson;
+use crate::lesson_4_iterators::IteratorLesson;
+use crate::lesson_5_trait_objects::TraitObjectLesson;
+use crate::lesson_6_variance::VarianceLesson;
+use crate::lesson_7_closures::ClosureLesson;
+use crate::lesson_8_threads::ThreadLesson;
+use crate::lesson_9_self_referential::SelfReferentialLesson;
+use crate::quiz::Question;
+
+/// Every lesson module registers itself here by implementing `Lesson`.
+/// The registry below is the single place a new lesson must be added,
+/// main and any tooling walk this list rather than calling lessons directly.
+pub(crate) trait Lesson: Sync {
+    /// Lesson number as printed in the banners, eg 1 for "lesson 1 example 2"
+    fn id(&self) -> u32;
+    fn title(&self) -> &'static str;
+    /// Terms from the Vocabulary block, each one defined in the glossary
+    fn vocabulary(&self) -> &'static [&'static str];
+    /// Examples in the order they are presented
+    fn examples(&self) -> &'static [Example];
+    /// The lesson file itself, so tooling can check claims made in its comments
+    fn source(&self) -> &'static str;
+    /// Questions about the examples, asked by the quiz command
+    fn quiz(&self) -> &'static [Question] {
+        &[]
+    }
+}
+
+/// A single runnable example, the id matches the banner so "3.5" is a valid id.
+pub(crate) struct Example {
+    pub(crate) id: &'static str,
+    pub(crate) title: &'static str,
+    pub(crate) run: fn(),
+}
+
+static LESSONS: &[&dyn Lesson] = &[
+    &ReferenceLesson,
+    &StructLesson,
+    &FunctionLesson,
+    &IteratorLesson,
+    &TraitObjectLesson,
+    &VarianceLesson,
+    &ClosureLesson,
+    &ThreadLesson,
+    &SelfReferentialLesson,
+];
+
+pub(crate) fn lessons() -> &'static [&'static dyn Lesson] {
+    LESSONS
+}
+
+pub(crate) fn banner(lesson: &dyn Lesson, example: &Example) -> String {
+    format!(" --------------- lesson {} example {} ---------------", lesson.id(), example.id)
+}
+
+pub(crate) fn print_header(lesson: &dyn Lesson) {
+    println!(" =============== lesson {}: {} ===============", lesson.id(), lesson.title());
+    for term in lesson.vocabulary() {
+        match glossary::lookup(term) {
+            Some(entry) => println!("   {}: {}", entry.term, entry.definition),
+            None => println!("   {}", term),
+        }
+    }
+}
+
+/// Every example of every lesson in presentation order.
+pub(crate) fn all_examples() -> Vec<(&'static dyn Lesson, &'static Example)> {
+    lessons().iter()
+             .flat_map(|lesson| lesson.examples().iter().map(move |example| (*lesson, example)))
+             .collect()
+}
+
+pub(crate) fn matches(selector: &Selector, lesson: &dyn Lesson, example: &Example) -> bool {
+    selector.lesson == lesson.id()
+        && selector.example.as_deref().is_none_or(|id| id == example.id)
+}
+
+/// Resolves a command line selection against the registry, keeping presentation order.
+pub(crate) fn select(selection: &Selection) -> Result<Vec<(&'static dyn Lesson, &'static Example)>, String> {
+    let all = all_examples();
+    let position = |selector: &Selector, last: bool| {
+        let hit = |(l, e): &(&dyn Lesson, &Example)| matches(selector, *l, e);
+        let index = if last { all.iter().rposition(hit) } else { all.iter().position(hit) };
+        index.ok_or_else(|| format!("no lesson or example matches '{}'", selector))
+    };
+    for selector in &selection.selectors {
+        position(selector, false)?;
+    }
+    let from = selection.from.as_ref().map(|s| position(s, false)).transpose()?.unwrap_or(0);
+    let to = selection.to.as_ref().map(|s| position(s, true)).transpose()?.unwrap_or(all.len() - 1);
+    if from > to {
+        // both ends are known to exist here
+        let (start, end) = (selection.from.as_ref().unwrap(), selection.to.as_ref().unwrap());
+        return Err(format!("--from {} comes after --to {}, nothing would be selected", start, end));
+    }
+
+    Ok(all.into_iter()
+          .enumerate()
+          .filter(|(i, _)| (from..=to).contains(i))
+          .filter(|(_, (l, e))| selection.selectors.is_empty()
+                                || selection.selectors.iter().any(|s| matches(s, *l, e)))
+          .map(|(_, pair)| pair)
+          .collect())
+}
+
+/// Runs the examples, introducing a lesson whenever we start from its first example.
+pub(crate) fn run_selected(selected: &[(&dyn Lesson, &Example)]) {
+    for (lesson, example) in selected {
+        if std::ptr::eq(*example, &lesson.examples()[0]) {
+            print_header(*lesson);
+        }
+        run_example(*lesson, example);
+    }
+}
+
+pub(crate) fn print_list() {
+    for lesson in lessons() {
+        println!("lesson {}: {}", lesson.id(), lesson.title());
+        for example in lesson.examples() {
+            println!("  {:<6} {}", format!("{}.{}", lesson.id(), example.id), example.title);
+        }
+    }
+}
+
+pub(crate) fn run_example(lesson: &dyn Lesson, example: &Example) {
+    println!("{}", banner(lesson, example));
+    (example.run)();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_ids_are_unique() {
+        let mut ids: Vec<u32> = lessons().iter().map(|l| l.id()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(lessons().len(), ids.len());
+
+        for lesson in lessons() {
+            assert!(!lesson.examples().is_empty(), "lesson {} has no examples", lesson.id());
+            let mut example_ids: Vec<&str> = lesson.examples().iter().map(|e| e.id).collect();
+            example_ids.sort();
+            example_ids.dedup();
+            assert_eq!(lesson.examples().len(), example_ids.len());
+        }
+    }
+
+    fn ids(selection: &Selection) -> Vec<String> {
+        select(selection).unwrap()
+                         .iter()
+                         .map(|(l, e)| format!("{}.{}", l.id(), e.id))
+                         .collect()
+    }
+
+    fn selector(text: &str) -> Selector {
+        Selector::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_select_single_example() {
+        let selection = Selection { selectors: vec![selector("2.3.5")], ..Default::default() };
+        assert_eq!(vec!["2.3.5"], ids(&selection));
+    }
+
+    #[test]
+    fn test_select_range_of_lessons() {
+        let selection = Selection { from: Some(selector("2")), to: Some(selector("3")), ..Default::default() };
+        let selected = ids(&selection);
+        assert_eq!("2.1", selected[0]);
+        assert_eq!("3.6", selected[selected.len() - 1]);
+        assert!(!selected.iter().any(|id| id.starts_with("1.")));
+    }
+
+    #[test]
+    fn test_select_unknown_is_an_error() {
+        let selection = Selection { selectors: vec![selector("2.9")], ..Default::default() };
+        assert!(select(&selection).is_err());
+    }
+
+    #[test]
+    fn test_select_backwards_range_is_an_error() {
+        let selection = Selection { from: Some(selector("3")), to: Some(selector("2")), ..Default::default() };
+        let message = select(&selection).err().unwrap();
+        assert!(message.contains("--from 3 comes after --to 2"), "{}", message);
+
+        let selection = Selection { from: Some(selector("2.3")), to: Some(selector("2")), ..Default::default() };
+        assert_eq!(vec!["2.3", "2.3.5"], ids(&selection));
+    }
+}
@@ -1,3 +1,7 @@
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
 
 /***********************************************************/
 /* Lesson 1: Reference Review - Borrowing and scope */
@@ -35,7 +39,7 @@
 ///              usage.  It could even be larger than a scope by naming the lifetime and
 ///              defining it outside the scope.
 ///
-/// Elision:     The compiler's ability to infer lifetimes based on the structure of the code
+/// Elision:     The compiler's ability to infer lifetimes based on the structure of the code.
 ///              Similar to the english contraction "I'm" vs "I Am"
 ///
 ///              It is pronounced as "ih-LIZH-uhn" where:
@@ -55,9 +59,111 @@
 /* Lesson 1: Reference Review - Borrowing and scope */
 ////////////////////////////////////////////////////////////////
 
-pub(crate) fn examples() {
+pub(crate) struct ReferenceLesson;
 
-    println!(" --------------- lesson 1 example 1 ---------------");
+impl Lesson for ReferenceLesson {
+    fn id(&self) -> u32 {
+        1
+    }
+    fn title(&self) -> &'static str {
+        "Reference Review - Borrowing and scope"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Immutable Reference",
+            "Mutable Reference",
+            "Lifetime",
+            "Elision",
+            "Elided",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "many immutable references", run: example_1 },
+            Example { id: "2", title: "one mutable reference", run: example_2 },
+            Example { id: "3", title: "passing a reference to a function", run: example_3 },
+            Example { id: "4", title: "returning an elided reference", run: example_4 },
+            Example { id: "5", title: "the 'static lifetime", run: example_5 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_1_reference.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "1",
+                prompt: "Two references to data are still printed after this drop.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_1_example_1_drop.rs"),
+                    compiles: false,
+                },
+                explanation: "data must outlive every reference to it, dropping it here would leave reference1 and reference2 pointing at freed memory when they are printed",
+            },
+            Question {
+                example: "2",
+                prompt: "How many mutable references to data may exist at one time?",
+                kind: Kind::Choice {
+                    options: &["as many as you like, as long as they are not used at once",
+                               "exactly one",
+                               "one per block"],
+                    answer: 1,
+                },
+                explanation: "while reference1 is live it is the only way to reach data, no other reference, shared or mutable, may exist beside it",
+            },
+            Question {
+                example: "2",
+                prompt: "data is read again while reference1 is still in scope.",
+                kind: Kind::Compiles {
+                    snippet: "\
+fn main() {
+    let mut data = String::from(\"Hello\");
+    let reference1 = &mut data;
+    reference1.push_str(\" World\");
+    println!(\"reference1: {}\", reference1);
+    println!(\"data: {}\", &data);
+}
+",
+                    compiles: true,
+                },
+                explanation: "reference1 is still in scope but never used again, so its borrow already ended on the line above and data may be read",
+            },
+            Question {
+                example: "4",
+                prompt: "In fn cant_make_ref(my_text: &String) -> &String, what was inferred for the returned reference?",
+                kind: Kind::Choice {
+                    options: &["'static",
+                               "the lifetime of the my_text reference",
+                               "a new lifetime that ends when the function returns"],
+                    answer: 1,
+                },
+                explanation: "one input reference, so elision gives the result the same lifetime as my_text",
+            },
+            Question {
+                example: "4",
+                prompt: "This version takes my_text by value and returns &my_text.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_1_example_4_return_owned.rs"),
+                    compiles: false,
+                },
+                explanation: "an owned my_text would be dropped when the function returns, so &my_text would point at a value that no longer exists",
+            },
+            Question {
+                example: "5",
+                prompt: "Why can make_string take a &'static str?",
+                kind: Kind::Choice {
+                    options: &["the String it returns is 'static",
+                               "the literal bytes are in the binary, valid for the whole program",
+                               "'static means the reference can never be dropped"],
+                    answer: 1,
+                },
+                explanation: "these literal bytes are in the binary",
+            },
+        ]
+    }
+}
+
+fn example_1() {
     {
         let data = String::from("Hello");
         let reference1 = &data;
@@ -68,9 +174,9 @@ pub(crate) fn examples() {
         println!("reference2: {}", reference2);
         drop(data);
     }
+}
 
-
-    println!(" --------------- lesson 1 example 2 ---------------");
+fn example_2() {
     {
         let mut data = String::from("Hello");
 
@@ -81,9 +187,9 @@ pub(crate) fn examples() {
         println!("reference1: {}", reference1);
         println!("data: {}", &data);
     }
+}
 
-
-    println!(" --------------- lesson 1 example 3 ---------------");
+fn example_3() {
     fn take_ref(my_text: &String) {
         println!("{:?}",my_text); //borrow ref and return nothing
     }
@@ -92,9 +198,9 @@ pub(crate) fn examples() {
         take_ref(&s); //note the owned value s must be held while ref was used
         println!("{:?}",s);
     }
+}
 
-
-    println!(" --------------- lesson 1 example 4 ---------------");
+fn example_4() {
     fn cant_make_ref(my_text: &String) -> &String {
         my_text //we can NOT pass in owned object and return &my_text, why?
     }
@@ -103,8 +209,9 @@ pub(crate) fn examples() {
         let r = cant_make_ref(&s);
         println!("{:?}",r);
     }
+}
 
-    println!(" --------------- lesson 1 example 5 ---------------");
+fn example_5() {
     //what is a &'static lifetime ?
     //it may be tempting to use 'static for everything; but it is not a good idea
     fn make_string(my_text: &'static str) -> String {
@@ -114,7 +221,6 @@ pub(crate) fn examples() {
         let message = make_string("message"); // these literal bytes are in the binary
         println!("{:?}",message);
     }
-
 }
 
 // For more 'details' read the Rustonomicon:  https://doc.rust-lang.org/nomicon/lifetimes.html
@@ -149,32 +255,35 @@ pub(crate) fn examples() {
 //    the lifetime of self is assigned to all elided output lifetimes.
 
 
-/*  Example from The Rustonomicon      https://doc.rust-lang.org/nomicon/lifetimes.html
-
-fn print(s: &str);                                      // elided
-fn print<'a>(s: &'a str);                               // expanded  (IDEs often show this)
-
-fn debug(lvl: usize, s: &str);                          // elided
-fn debug<'a>(lvl: usize, s: &'a str);                   // expanded
+//  Example from The Rustonomicon      https://doc.rust-lang.org/nomicon/lifetimes.html
+//  Kept as data so `cargo test` can run each pair through the elision expander and rustc.
 
-fn substr(s: &str, until: usize) -> &str;               // elided
-fn substr<'a>(s: &'a str, until: usize) -> &'a str;     // expanded
-
-fn get_str() -> &str;                                   // ILLEGAL
-
-fn frob(s: &str, t: &str) -> &str;                      // ILLEGAL (will see example later)
-
-fn get_mut(&mut self) -> &mut T;                        // elided
-fn get_mut<'a>(&'a mut self) -> &'a mut T;              // expanded
-
-fn args<T: ToCStr>(&mut self, args: &[T]) -> &mut Command                  // elided
-fn args<'a, 'b, T: ToCStr>(&'a mut self, args: &'b [T]) -> &'a mut Command // expanded
-
-fn new(buf: &mut [u8]) -> BufWriter;                    // elided
-fn new(buf: &mut [u8]) -> BufWriter<'_>;                // elided (with `rust_2018_idioms`)
-fn new<'a>(buf: &'a mut [u8]) -> BufWriter<'a>          // expanded
+/// An elided signature and what the compiler expands it to, None where it is ILLEGAL.
+pub(crate) struct ElisionPair {
+    pub(crate) elided: &'static str,
+    pub(crate) expanded: Option<&'static str>,
+}
 
- */
+pub(crate) const RUSTONOMICON_ELISION: &[ElisionPair] = &[
+    ElisionPair { elided:   "fn print(s: &str);",
+                  expanded: Some("fn print<'a>(s: &'a str);") }, // IDEs often show this
+    ElisionPair { elided:   "fn debug(lvl: usize, s: &str);",
+                  expanded: Some("fn debug<'a>(lvl: usize, s: &'a str);") },
+    ElisionPair { elided:   "fn substr(s: &str, until: usize) -> &str;",
+                  expanded: Some("fn substr<'a>(s: &'a str, until: usize) -> &'a str;") },
+    ElisionPair { elided:   "fn get_str() -> &str;",
+                  expanded: None },
+    ElisionPair { elided:   "fn frob(s: &str, t: &str) -> &str;", // will see example later
+                  expanded: None },
+    ElisionPair { elided:   "fn get_mut(&mut self) -> &mut T;",
+                  expanded: Some("fn get_mut<'a>(&'a mut self) -> &'a mut T;") },
+    ElisionPair { elided:   "fn args<T: ToCStr>(&mut self, args: &[T]) -> &mut Command;",
+                  expanded: Some("fn args<'a, 'b, T: ToCStr>(&'a mut self, args: &'b [T]) -> &'a mut Command;") },
+    ElisionPair { elided:   "fn new(buf: &mut [u8]) -> BufWriter;",
+                  expanded: Some("fn new<'a>(buf: &'a mut [u8]) -> BufWriter<'a>;") },
+    ElisionPair { elided:   "fn new(buf: &mut [u8]) -> BufWriter<'_>;", // with `rust_2018_idioms`
+                  expanded: Some("fn new<'a>(buf: &'a mut [u8]) -> BufWriter<'a>;") },
+];
 
 //  Note the last example, the underscore is a placeholder for the lifetime. It is not clear that
 //  BufWriter is borrowing the buffer for the lifetime of the buffer. The lifetime is inferred
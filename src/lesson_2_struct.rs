@@ -1,3 +1,9 @@
+#![allow(dead_code, clippy::empty_line_after_doc_comments, clippy::needless_arbitrary_self_type,
+         clippy::needless_borrow)]
+
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
+
 
 
 /***********************************************************/
@@ -26,9 +32,83 @@
 /* Lesson 2: Struct and self - Simple lifetime */
 ////////////////////////////////////////////////////////////////
 
-pub(crate) fn examples() {
+pub(crate) struct StructLesson;
+
+impl Lesson for StructLesson {
+    fn id(&self) -> u32 {
+        2
+    }
+    fn title(&self) -> &'static str {
+        "Struct and self"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Struct",
+            "Implementation",
+            "Method",
+            "Ownership",
+            "Drop",
+            "Lifetime",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "struct without references", run: example_1 },
+            Example { id: "2", title: "struct with unified lifetimes", run: example_2 },
+            Example { id: "3", title: "struct with split lifetimes", run: example_3 },
+            Example { id: "3.5", title: "methods over split lifetimes", run: example_3_5 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_2_struct.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "2",
+                prompt: "UnifiedStruct { a: a_ref, b: &b } has one lifetime 'a. What was inferred for it?",
+                kind: Kind::Choice {
+                    options: &["the lifetime of a, the longer lived of the two",
+                               "a region where both a and b are still valid",
+                               "'static"],
+                    answer: 1,
+                },
+                explanation: "within this struct we tied a and b together",
+            },
+            Question {
+                example: "2",
+                prompt: "b is dropped while the UnifiedStruct holding &b is still used.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_2_example_2_drop_b.rs"),
+                    compiles: false,
+                },
+                explanation: "s.a is used below and shares 'a with s.b, so b must stay alive until then",
+            },
+            Question {
+                example: "3",
+                prompt: "some_strange_function returns &'a String. Why may a_ref outlive b_string?",
+                kind: Kind::Choice {
+                    options: &["the struct is copied into a_ref",
+                               "only 'a is tied to the result, 'b is free to end with b_string",
+                               "b_string is not dropped until the outer block ends"],
+                    answer: 1,
+                },
+                explanation: "the result only carries 'a, the lifetime of a_string, so 'b may end with this block",
+            },
+            Question {
+                example: "3.5",
+                prompt: "a_ref is assigned from consume_return_b and used after b_string is gone.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_2_example_3_5_consume_return_b.rs"),
+                    compiles: false,
+                },
+                explanation: "if we assign outside our scope then the compiler discovers the problem because data holds b and b_string is restricted to this scope.",
+            },
+        ]
+    }
+}
 
-    println!(" --------------- lesson 2 example 1 ---------------");
+fn example_1() {
     {
         //we have no references and do not need any lifetimes
         struct SimpleStruct {
@@ -68,9 +148,10 @@ pub(crate) fn examples() {
             println!("{:?} {:?}", ref_b, s.b);
         }
     }
+}
 
+fn example_2() {
     {
-        println!(" --------------- lesson 2 example 2 ---------------");
         //I could use str but used String for simplicity
 
         //struct with unified lifetimes
@@ -120,117 +201,122 @@ pub(crate) fn examples() {
           println!("{:?}",a_ref); //what was "inferred" here?
         }
     }
+}
 
+fn example_3() {
+    //struct with split lifetimes
 
-    {
-        println!(" ---------------  lesson 2 example 3  ---------------");
+    #[derive(Debug)]
+    struct SplitStruct<'a,'b> {
+        a: &'a String,
+        b: &'b String
+    }
 
-        //struct with split lifetimes
+    fn some_strange_function<'a,'b>(d: SplitStruct<'a,'b>) -> &'a String {
+        let SplitStruct {a, b} = d; //this is a "partial move" and a "destructuring"
+        println!("{:?} {:?}",a,b);
+        a
+    }
 
-        #[derive(Debug)]
-        struct SplitStruct<'a,'b> {
-            a: &'a String,
-            b: &'b String
-        }
+    {
+        let a_string = String::from("aaa");
+        let a_ref;
+        {
+            let b_string = String::from("bbb");
+            let data = SplitStruct {a: &a_string, b: &b_string};
+            println!("{:?}",&data);
+            a_ref = some_strange_function(data);
 
-        fn some_strange_function<'a,'b>(d: SplitStruct<'a,'b>) -> &'a String {
-            let SplitStruct {a, b} = d; //this is a "partial move" and a "destructuring"
-            println!("{:?} {:?}",a,b);
-            a
         }
+        println!("{:?}",a_ref);
 
-        {
-            let a_string = String::from("aaa");
-            let a_ref;
-            {
-                let b_string = String::from("bbb");
-                let data = SplitStruct {a: &a_string, b: &b_string};
-                println!("{:?}",&data);
-                a_ref = some_strange_function(data);
-
-            }
-            println!("{:?}",a_ref);
+    }
+}
 
-        }
+fn example_3_5() {
+    //the same struct as example 3, now with methods
 
-        println!(" ---------------  lesson 2 example 3.5  ---------------");
+    #[derive(Debug)]
+    struct SplitStruct<'a,'b> {
+        a: &'a String,
+        b: &'b String
+    }
 
-        impl <'a,'b> SplitStruct<'a,'b> {
-            fn consume(self) {
-            }
-            fn consume_return_a(self) -> &'a String {
-                self.a
-            }
-            fn consume_return_b(self) -> &'b String {
-                self.b
-            }
-            fn process_a(self: &Self) -> &String {
-                self.a
-            }
-            fn process_b(self: &Self) -> &String {
-                self.b
-            }
-            fn update_a(self: &mut Self, a: &'a String) -> &String {
-                self.a = a;
-                a
-            }
-            fn update_b(self: &mut Self, b: &'b String) -> &String {
-                self.b = b;
-                b
-            }
+    impl <'a,'b> SplitStruct<'a,'b> {
+        fn consume(self) {
+        }
+        fn consume_return_a(self) -> &'a String {
+            self.a
+        }
+        fn consume_return_b(self) -> &'b String {
+            self.b
+        }
+        fn process_a(self: &Self) -> &String {
+            self.a
+        }
+        fn process_b(self: &Self) -> &String {
+            self.b
         }
+        fn update_a(self: &mut Self, a: &'a String) -> &String {
+            self.a = a;
+            a
+        }
+        fn update_b(self: &mut Self, b: &'b String) -> &String {
+            self.b = b;
+            b
+        }
+    }
 
+    {
+        let a_string = String::from("aaa");
+        let a_ref;
         {
-            let a_string = String::from("aaa");
-            let a_ref;
-            {
-                let b_string = String::from("bbb");
-                let data = SplitStruct {a: &a_string, b: &b_string};
+            let b_string = String::from("bbb");
+            let data = SplitStruct {a: &a_string, b: &b_string};
 
-                a_ref = data.consume_return_a();
-               // a_ref = data.consume_return_b(); //this can not work. what was inferred?
+            a_ref = data.consume_return_a();
+           // a_ref = data.consume_return_b(); //this can not work. what was inferred?
 
-            }
-            println!("{:?}",a_ref);
         }
+        println!("{:?}",a_ref);
+    }
 
-        {
-            let a_string = String::from("aaa");
-            let a_ref;
-            let text = String::from("");
+    {
+        let a_string = String::from("aaa");
+        let a_ref;
+        let text = String::from("");
 
-            {
-                let b_string = String::from("bbb");
-                let mut data = SplitStruct {a: &a_string, b: &b_string};
+        {
+            let b_string = String::from("bbb");
+            let mut data = SplitStruct {a: &a_string, b: &b_string};
 
-                //if we assign outside our scope then the compiler discovers the problem
-                // because data holds b and b_string is restricted to this scope.
-                //a_ref = data.process_a(); //this is not allowed, it was not moved out of data
+            //if we assign outside our scope then the compiler discovers the problem
+            // because data holds b and b_string is restricted to this scope.
+            //a_ref = data.process_a(); //this is not allowed, it was not moved out of data
 
 
-                let SplitStruct {a,b:_} = data; // this is a "partial move"
+            let SplitStruct {a,b:_} = data; // this is a "partial move"
 
-                a_ref = a; //here we can share the &str out of this scope with data
+            a_ref = a; //here we can share the &str out of this scope with data
 
-                // the rest of these lines are probably very bad practice
-                // BUT lets do it anyway. a future version of Rust may stop us.
+            // the rest of these lines are probably very bad practice
+            // BUT lets do it anyway. a future version of Rust may stop us.
 
-                println!("a:{:?} b:{:?}",data.a,data.b); //questionable practice
+            println!("a:{:?} b:{:?}",data.a,data.b); //questionable practice
 
-                data.a=&text; //questionable practice
+            data.a=&text; //questionable practice
 
-                let b_ref = data.process_b(); //questionable practice
-                println!("{:?} ",b_ref);
+            let b_ref = data.process_b(); //questionable practice
+            println!("{:?} ",b_ref);
 
-                let x_ref = data.process_a(); //questionable practice
-                println!("{:?} ",x_ref);
+            let x_ref = data.process_a(); //questionable practice
+            println!("{:?} ",x_ref);
 
-                println!("{:?}",data);
-                let x = data.consume_return_b(); //questionable practice
-                println!("{:?}",x);
+            println!("{:?}",data);
+            let x = data.consume_return_b(); //questionable practice
+            println!("{:?}",x);
 
-            };
-            println!("{:?}",a_ref);
-        }
+        };
+        println!("{:?}",a_ref);
     }
-}
\ No newline at end of file
+}
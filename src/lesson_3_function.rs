@@ -1,3 +1,9 @@
+#![allow(dead_code, dropping_references, clippy::empty_line_after_doc_comments,
+         clippy::needless_lifetimes, clippy::unnecessary_sort_by, clippy::useless_vec)]
+
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
+
 
 /***********************************************************/
 /* Lesson 3: Complex lifetime applications*/
@@ -25,9 +31,82 @@
 /* Lesson 3: Functions */
 ////////////////////////////////////////////////////////////////
 
-pub(crate) fn examples() {
+pub(crate) struct FunctionLesson;
+
+impl Lesson for FunctionLesson {
+    fn id(&self) -> u32 {
+        3
+    }
+    fn title(&self) -> &'static str {
+        "Complex lifetime applications"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Lifetime Elision",
+            "Lifetime Subtyping",
+            "Higher-Rank Trait Bounds (HRTBs)",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "lifetime as a guard", run: example_1 },
+            Example { id: "2", title: "returning the shortest string", run: example_2 },
+            Example { id: "3", title: "only the returned reference needs a lifetime", run: example_3 },
+            Example { id: "4", title: "naming lifetimes for clarity", run: example_4 },
+            Example { id: "5", title: "returning a reference to a newly owned value", run: example_5 },
+            Example { id: "6", title: "higher-rank trait bounds", run: example_6 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_3_function.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "1",
+                prompt: "s is changed while the reference returned with its length is still held.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_3_example_1_push_str.rs"),
+                    compiles: false,
+                },
+                explanation: "This line will cause a compile-time error because `s` is still borrowed immutably",
+            },
+            Question {
+                example: "2",
+                prompt: "shortest_length gives x, y and z the same 'a. How long may its result be used?",
+                kind: Kind::Choice {
+                    options: &["until x is dropped",
+                               "until the shortest lived of x, y and z is dropped",
+                               "for the rest of the program"],
+                    answer: 1,
+                },
+                explanation: "Rust ensures that none of these references are used after the shortest-lived owned value expires.",
+            },
+            Question {
+                example: "5",
+                prompt: "attempted_return_owned takes text by value and returns &text.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_3_example_5_return_owned.rs"),
+                    compiles: false,
+                },
+                explanation: "we had to be clear about its lifetime but could still not return it",
+            },
+            Question {
+                example: "6",
+                prompt: "What does for<'a, 'goober> add to the bound on the closure?",
+                kind: Kind::Choice {
+                    options: &["the closure must be 'static",
+                               "the closure must work for whatever lifetimes it is called with",
+                               "the closure borrows from a struct with these lifetimes"],
+                    answer: 1,
+                },
+                explanation: "HRTBs allow more flexibility, letting you write more generic and reusable code that works with references of any lifetime",
+            },
+        ]
+    }
+}
 
-    println!(" --------------- lesson 3 example 1 ---------------");
+fn example_1() {
     //using lifetime as a guard
 
     fn get_length_with_lifetime<'a>(s: &'a str) -> (usize, &'a str) {
@@ -56,8 +135,9 @@ pub(crate) fn examples() {
         s.push_str(" New text");
         println!("String after modification: {}", s);
     }
+}
 
-    println!(" --------------- lesson 3 example 2 ---------------");
+fn example_2() {
     // classic example of returning the shortest string
     // When you have multiple references with the same named lifetime, Rust ensures that
     // none of these references are used after the shortest-lived owned value expires. This
@@ -87,7 +167,9 @@ pub(crate) fn examples() {
         vec.sort_by(|a, b| a.len().cmp(&b.len()));
         vec[0]
     }
-    println!(" --------------- lesson 3 example 3 ---------------");
+}
+
+fn example_3() {
     //if we only return one of the references that is the only thing needing a lifetime
     fn shortest_length_broken<'a>(x: &'a String,
                                   y: & String,
@@ -102,9 +184,9 @@ pub(crate) fn examples() {
         let shortest = shortest_length_broken(&x, &y, &z);
         println!("Shortest string broken: {}", shortest);
     }
+}
 
-
-    println!(" --------------- lesson 3 example 4 ---------------");
+fn example_4() {
     fn do_something1(_x: String, y: &String) -> &String { // Elided, we have only one reference
         y
     }
@@ -129,8 +211,9 @@ pub(crate) fn examples() {
         println!("Result: {}", y_ref);
 
     }
+}
 
-    println!(" --------------- lesson 3 example 5 ---------------");
+fn example_5() {
     //how we could create a new owned value yet return a reference to it
     //this is a bit of a strange case, but it is possible
 
@@ -155,8 +238,9 @@ pub(crate) fn examples() {
       println!("{}",hold_and_ref(&mut holding,String::from("world")));
       println!("{}",holding.text);
     }
+}
 
-    println!(" --------------- lesson 3 example 6 ---------------");
+fn example_6() {
     // HRTB (Higher-Rank Trait Bounds) in Action
     // There is very little documentation on higher-ranked lifetimes, so let's break this down.
 
@@ -189,4 +273,3 @@ pub(crate) fn examples() {
         // trait, which would make the code less flexible.
     }
 }
-
@@ -0,0 +1,214 @@
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
+
+
+/***********************************************************/
+/* Lesson 4: Iterators - borrowing through adaptors */
+/***********************************************************/
+
+/// Iterators are where most of us first meet lifetimes we did not write. Every call to
+/// iter() hands back a value that borrows the collection, every adaptor like filter or
+/// map wraps that borrow in another struct, and the borrow lasts as long as the last
+/// adaptor in the chain is still in use.
+///
+/// Once you see an iterator as "a struct holding a reference" the rules from lessons 1
+/// and 2 apply unchanged. The collection can not be dropped or changed while an iterator
+/// over it is alive, and anything the iterator yields can not outlive the collection.
+
+/********************/
+/*   Vocabulary     */
+/********************/
+
+/// Iterator: A value with a next() method that yields items one at a time until it
+///           returns None.
+/// Iterator Adaptor: A method such as map, filter or zip that wraps one iterator in
+///                   another. The adaptor holds the inner iterator and any borrow it carries.
+/// Borrowing Iterator: The iterator from iter() or iter_mut(), it yields references and
+///                     the collection is still ours when it is done.
+/// Consuming Iterator: The iterator from into_iter() on an owned collection, it yields the
+///                     items by value and the collection is moved into it.
+/// impl Trait: A return type that names a trait instead of the concrete type, the caller
+///             only knows it is "some Iterator", including which lifetimes it may hold.
+
+////////////////////////////////////////////////////////////////
+/* Lesson 4: Iterators */
+////////////////////////////////////////////////////////////////
+
+pub(crate) struct IteratorLesson;
+
+impl Lesson for IteratorLesson {
+    fn id(&self) -> u32 {
+        4
+    }
+    fn title(&self) -> &'static str {
+        "Iterators and borrowing adaptors"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Iterator",
+            "Iterator Adaptor",
+            "Borrowing Iterator",
+            "Consuming Iterator",
+            "impl Trait",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "iter() borrows, into_iter() consumes", run: example_1 },
+            Example { id: "2", title: "returning an iterator that borrows", run: example_2 },
+            Example { id: "3", title: "a hand written iterator over a slice", run: example_3 },
+            Example { id: "4", title: "iter_mut never hands out the same item twice", run: example_4 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_4_iterators.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "1",
+                prompt: "names is printed again after a for loop over names.into_iter().",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_4_example_1_use_after_into_iter.rs"),
+                    compiles: false,
+                },
+                explanation: "into_iter() moves names into the iterator, each name is an owned String",
+            },
+            Question {
+                example: "2",
+                prompt: "Why does evens need + 'a when its items are plain i32 values?",
+                kind: Kind::Choice {
+                    options: &["i32 is not 'static",
+                               "the iterator still holds the borrow of numbers even though its items are owned",
+                               "filter requires every closure to be 'a"],
+                    answer: 1,
+                },
+                explanation: "the iterator itself still holds &'a [i32]",
+            },
+            Question {
+                example: "4",
+                prompt: "Two calls to numbers.iter_mut() each keep a &mut to an item.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_4_example_4_two_iter_mut.rs"),
+                    compiles: false,
+                },
+                explanation: "one iter_mut hands out each item exactly once",
+            },
+        ]
+    }
+}
+
+fn example_1() {
+    {
+        let names = vec![String::from("ann"), String::from("bob")];
+
+        // iter() borrows names, each name is a &String
+        for name in names.iter() {
+            println!("borrowed: {}", name);
+        }
+        println!("still ours: {:?}", names); // the borrow ended with the loop
+
+        // into_iter() moves names into the iterator, each name is an owned String
+        for name in names.into_iter() {
+            println!("owned: {}", name);
+        }
+        //println!("{:?}", names); // this will cause an error
+    }
+}
+
+fn example_2() {
+    // the returned iterator holds a borrow of words, so it can not outlive 'a
+    fn longer_than<'a>(words: &'a [String], len: usize) -> impl Iterator<Item = &'a String> + 'a {
+        words.iter().filter(move |word| word.len() > len)
+    }
+
+    // the items are owned i32 values but the iterator itself still holds &'a [i32]
+    // without + 'a the compiler will not let the hidden borrow escape
+    //fn evens(numbers: &[i32]) -> impl Iterator<Item = i32> { // this will cause an error
+    fn evens<'a>(numbers: &'a [i32]) -> impl Iterator<Item = i32> + 'a {
+        numbers.iter().copied().filter(|n| n % 2 == 0)
+    }
+
+    {
+        let words = vec![String::from("iterator"), String::from("map"), String::from("lifetime")];
+        let long: Vec<&String> = longer_than(&words, 3).collect();
+        //drop(words); // we still hold references into words in long
+        println!("{:?}", long);
+
+        let numbers = [1, 2, 3, 4, 5, 6];
+        let even: Vec<i32> = evens(&numbers).collect();
+        println!("{:?}", even); // owned values, numbers could be dropped now
+    }
+}
+
+// Our own iterator is just a struct holding a reference like the ones in lesson 2.
+// Each item borrows from the slice with 'a, not from the iterator, so the items
+// may be kept after the iterator is gone.
+struct Pairs<'a, T> {
+    items: &'a [T],
+    index: usize,
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> { // &mut self is short lived, 'a is not
+        let pair = (self.items.get(self.index)?, self.items.get(self.index + 1)?);
+        self.index += 1;
+        Some(pair)
+    }
+}
+
+fn example_3() {
+    {
+        let steps = [String::from("borrow"), String::from("use"), String::from("release")];
+        let kept: Vec<(&String, &String)>;
+        {
+            let pairs = Pairs { items: &steps, index: 0 };
+            kept = pairs.collect(); // pairs is consumed here
+        }
+        for (from, to) in &kept {
+            println!("{} then {}", from, to);
+        }
+    }
+}
+
+// iter_mut yields &'a mut T from &mut self. If we write that with get_mut the item
+// borrows self, which only lives for one call to next, and the compiler reports
+// "lifetime may not live long enough". Taking the slice out of self and splitting
+// off the first item proves each &mut is handed out once.
+struct EachMut<'a, T> {
+    items: &'a mut [T],
+}
+
+impl<'a, T> Iterator for EachMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let items = std::mem::take(&mut self.items); // leaves an empty slice behind
+        let (first, rest) = items.split_first_mut()?;
+        self.items = rest;
+        Some(first)
+    }
+}
+
+fn example_4() {
+    {
+        let mut numbers = vec![1, 2, 3];
+
+        // one iter_mut hands out each item exactly once so these never overlap
+        let mut each = numbers.iter_mut();
+        let first = each.next().unwrap();
+        let second = each.next().unwrap();
+        //let second = numbers.iter_mut().nth(1).unwrap(); // this will cause an error
+        *first += *second;
+        println!("{:?}", numbers);
+
+        for n in (EachMut { items: &mut numbers }) {
+            *n *= 10;
+        }
+        println!("{:?}", numbers);
+    }
+}
@@ -0,0 +1,218 @@
+#![allow(clippy::empty_line_after_doc_comments, clippy::type_complexity)]
+
+use std::fmt::Display;
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
+
+
+/***********************************************************/
+/* Lesson 5: Trait Objects - the lifetime you did not write */
+/***********************************************************/
+
+/// A trait object like dyn Display hides the concrete type behind a pointer, and with the
+/// type goes everything the compiler knew about what that value borrows. So every trait
+/// object carries a lifetime bound saying how long whatever is behind it may be used,
+/// and when we do not write one the compiler fills in a default.
+///
+/// The defaults follow the pointer. In a struct field or a function signature
+/// Box<dyn Trait> is Box<dyn Trait + 'static>, the box owns its value and could keep it
+/// forever, so the value may not borrow anything short lived. &'a dyn Trait is
+/// &'a (dyn Trait + 'a), a borrowed object may borrow for as long as we borrow it. When a
+/// box has to hold something borrowed we say so with Box<dyn Trait + 'a>. Inside a
+/// function body the compiler infers the bound like any other lifetime, which is why
+/// the surprise usually comes later, at the struct or the return type.
+
+/********************/
+/*   Vocabulary     */
+/********************/
+
+/// Trait Object: A value used through a pointer like Box<dyn Trait> or &dyn Trait, where
+///               only the trait is known and the concrete type is decided at runtime.
+/// Object Lifetime Bound: The + 'a in dyn Trait + 'a, how long anything the hidden value
+///                        borrows must stay valid.
+/// Default Object Lifetime Bound: The bound the compiler picks when none is written in a
+///                                signature or field, 'static inside a Box and 'a inside &'a.
+/// Boxed Closure: A closure stored as Box<dyn Fn(..)>, so closures with different captures
+///                can share one field or Vec.
+
+////////////////////////////////////////////////////////////////
+/* Lesson 5: Trait Objects */
+////////////////////////////////////////////////////////////////
+
+pub(crate) struct TraitObjectLesson;
+
+impl Lesson for TraitObjectLesson {
+    fn id(&self) -> u32 {
+        5
+    }
+    fn title(&self) -> &'static str {
+        "Trait objects and default object lifetime bounds"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Trait Object",
+            "Object Lifetime Bound",
+            "Default Object Lifetime Bound",
+            "Boxed Closure",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "Box<dyn Trait> means + 'static", run: example_1 },
+            Example { id: "2", title: "&'a dyn Trait borrows for 'a", run: example_2 },
+            Example { id: "3", title: "a struct of boxed closures that borrow", run: example_3 },
+            Example { id: "4", title: "boxing a borrowing closure as 'static", run: example_4 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_5_trait_objects.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "1",
+                prompt: "What does a Box<dyn Display> struct field mean when no bound is written?",
+                kind: Kind::Choice {
+                    options: &["Box<dyn Display + 'static>",
+                               "Box<dyn Display + 'a> for the lifetime of the box",
+                               "no bound at all, the box may hold anything"],
+                    answer: 0,
+                },
+                explanation: "with no bound written shelf.items holds Box<dyn Display + 'static>",
+            },
+            Question {
+                example: "4",
+                prompt: "A Box<dyn Fn(&str) -> &str> field is given a closure that captures &usize from main.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_5_example_4_borrowed_closure_static.rs"),
+                    compiles: false,
+                },
+                explanation: "a 'static box needs a closure that owns what it captures",
+            },
+        ]
+    }
+}
+
+// A field is a signature, the default applies: this is Vec<Box<dyn Display + 'static>>.
+struct Shelf {
+    items: Vec<Box<dyn Display>>,
+}
+
+fn example_1() {
+    {
+        let name = String::from("Ferris");
+        let mut shelf = Shelf { items: Vec::new() };
+
+        // the box owns a String, nothing is borrowed so 'static is fine
+        shelf.items.push(Box::new(name.clone()));
+        shelf.items.push(Box::new(42));
+
+        // a &String is Display too, but it borrows name which is not 'static
+        //shelf.items.push(Box::new(&name)); // this will cause an error
+        for item in &shelf.items {
+            println!("on the shelf: {}", item);
+        }
+
+        // in the body the bound is inferred, this box is Box<dyn Display + '_>
+        let shown: Box<dyn Display> = Box::new(&name);
+        println!("shown: {}", shown);
+        drop(shown); // the borrow of name ends with the box
+        println!("{} is still ours", name);
+    }
+}
+
+// No bound is written on either field, each gets the default for its pointer.
+// label is &'a (dyn Display + 'a) and fallback is Box<dyn Display + 'static>.
+struct Label<'a> {
+    label: &'a dyn Display,
+    fallback: Box<dyn Display>,
+}
+
+impl<'a> Label<'a> {
+    fn show(&self) -> String {
+        format!("{} (or {})", self.label, self.fallback)
+    }
+}
+
+fn example_2() {
+    {
+        let count = 3;
+        let title = String::from("iterators");
+
+        // borrowed objects of different types side by side in one Vec
+        let parts: Vec<&dyn Display> = vec![&count, &title];
+        for part in &parts {
+            println!("part: {}", part);
+        }
+
+        let label = Label { label: &title, fallback: Box::new("untitled") }; // a &'static str is 'static
+        println!("{}", label.show());
+    }
+}
+
+// The struct from lesson 3's apply_to_str, with the closures stored instead of
+// passed in. Fn(&str) -> &str is still higher-ranked, each call may bring a
+// string of any lifetime. The + 'a is only about what the closures capture.
+struct Pipeline<'a> {
+    steps: Vec<Box<dyn Fn(&str) -> &str + 'a>>,
+}
+
+impl<'a> Pipeline<'a> {
+    fn new() -> Pipeline<'a> {
+        Pipeline { steps: Vec::new() }
+    }
+
+    fn add_step(&mut self, step: impl Fn(&str) -> &str + 'a) {
+        self.steps.push(Box::new(step));
+    }
+
+    fn apply<'t>(&self, text: &'t str) -> &'t str {
+        self.steps.iter().fold(text, |text, step| step(text))
+    }
+}
+
+fn example_3() {
+    {
+        let prefix = String::from("warning: ");
+        let limit = 12;
+        let mut pipeline = Pipeline::new();
+
+        // both closures borrow locals, so pipeline is a Pipeline<'a> where 'a ends with them
+        pipeline.add_step(|s| s.strip_prefix(prefix.as_str()).unwrap_or(s));
+        pipeline.add_step(|s| &s[..s.len().min(limit)]);
+
+        println!("{}", pipeline.apply("warning: unused variable `x`"));
+        println!("{}", pipeline.apply("error: borrowed value"));
+        //drop(prefix); // pipeline still borrows prefix
+        println!("{}", pipeline.apply("warning: dead code"));
+    }
+}
+
+// The same idea without + 'a: the field defaults to 'static.
+struct StaticPipeline {
+    step: Box<dyn Fn(&str) -> &str>,
+}
+
+fn example_4() {
+    {
+        let skip = 2;
+        let skip_ref = &skip;
+
+        // the closure captures skip_ref which borrows skip, a local of this block
+        //let pipeline = StaticPipeline { step: Box::new(move |s| &s[*skip_ref..]) }; // this will cause an error
+
+        // a 'static box needs a closure that owns what it captures, copy the number in
+        let skip = *skip_ref;
+        let pipeline = StaticPipeline { step: Box::new(move |s| &s[skip..]) };
+        println!("{}", (pipeline.step)("> quoted reply"));
+
+        // Returning a boxed closure has the same default. Written as
+        // fn counter(text: &str) -> Box<dyn Fn() -> usize> the compiler says
+        // "lifetime may not live long enough" and suggests the + '_ below.
+        fn counter(text: &str) -> Box<dyn Fn() -> usize + '_> {
+            Box::new(move || text.len())
+        }
+        let text = String::from("borrowed");
+        println!("{}", counter(&text)());
+    }
+}
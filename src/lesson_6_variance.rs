@@ -0,0 +1,243 @@
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
+
+
+/***********************************************************/
+/* Lesson 6: Variance - why 'b: 'a works */
+/***********************************************************/
+
+/// In lesson 3 shortest_length_with_lifetime_subtyping took x: &'a String and y: &'b String
+/// with 'b: 'a and put all three in one vec. That only works because a reference that
+/// lives longer may be used where a shorter one is expected, a &'b String simply becomes a
+/// &'a String. The rule that decides when a type may be shrunk like this is called variance.
+///
+/// Every type is covariant, invariant or contravariant in each lifetime it holds.
+/// Covariant types like &'a T may shrink 'a. Invariant types like &'a mut T and
+/// Cell<&'a T> must keep 'a exactly, because something could be written through them.
+/// Contravariant types like fn(&'a T) go the other way, a function that accepts any
+/// reference may stand in for one that only needs a &'static one. A struct gets its
+/// variance from its fields, and PhantomData lets us choose it when no field says it.
+
+/********************/
+/*   Vocabulary     */
+/********************/
+
+/// Variance: How a type's lifetime parameter may change when the type is passed around,
+///           it can be covariant, invariant or contravariant.
+/// Covariant: The lifetime may be shortened, a &'static str can be used as a &'a str.
+/// Invariant: The lifetime must match exactly, neither longer nor shorter, as behind
+///            &mut T or Cell<T>.
+/// Contravariant: The lifetime may be lengthened, only seen in function arguments such
+///                as fn(&'a T).
+/// PhantomData: A zero sized field that tells the compiler the struct acts as if it held
+///              a value of the given type, lifetimes and variance included.
+
+////////////////////////////////////////////////////////////////
+/* Lesson 6: Variance */
+////////////////////////////////////////////////////////////////
+
+pub(crate) struct VarianceLesson;
+
+impl Lesson for VarianceLesson {
+    fn id(&self) -> u32 {
+        6
+    }
+    fn title(&self) -> &'static str {
+        "Variance and lifetime subtyping in depth"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Variance",
+            "Covariant",
+            "Invariant",
+            "Contravariant",
+            "PhantomData",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "&'a T is covariant", run: example_1 },
+            Example { id: "2", title: "&'a mut T is invariant", run: example_2 },
+            Example { id: "3", title: "Cell<&'a T> is invariant", run: example_3 },
+            Example { id: "4", title: "fn(&'a T) is contravariant", run: example_4 },
+            Example { id: "5", title: "PhantomData borrows on our behalf", run: example_5 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_6_variance.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "2",
+                prompt: "Why can feed not put a short lived &str into a &'static str variable?",
+                kind: Kind::Choice {
+                    options: &["&mut T is invariant so T stays &'static str",
+                               "feed would need a 'b: 'a bound",
+                               "String can never be borrowed as &str"],
+                    answer: 0,
+                },
+                explanation: "if &mut were covariant T could shrink to &'spike str and spike would be written into pet, which outlives it",
+            },
+            Question {
+                example: "4",
+                prompt: "A fn(&'static str) is stored in a variable of type fn(&str).",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_6_example_4_static_fn_as_any.rs"),
+                    compiles: false,
+                },
+                explanation: "print_static would be handed borrows it can not accept",
+            },
+            Question {
+                example: "5",
+                prompt: "data is dropped while a View built from it is still used.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_6_example_5_drop_under_view.rs"),
+                    compiles: false,
+                },
+                explanation: "view holds no reference, but its PhantomData<&'a T> keeps data borrowed while view is used",
+            },
+        ]
+    }
+}
+
+fn example_1() {
+    // 'static is longer than any 'short, so a &'static str can stand in for a &'short str.
+    // This is the same move lesson 3 made with 'b: 'a.
+    fn shorter<'short>(a: &'short str, b: &'short str) -> &'short str {
+        if a.len() <= b.len() { a } else { b }
+    }
+
+    {
+        let forever: &'static str = "static";
+        let local = String::from("local string");
+        println!("{}", shorter(forever, &local)); // forever shrinks to match &local
+    }
+
+    // The other way is not allowed, a short borrow can not become a 'static one.
+    {
+        let mut long: &'static str = "static";
+        {
+            let local = String::from("local");
+            //long = &local; // this will cause an error
+            println!("{} {}", long, local);
+        }
+        long = "still static";
+        println!("{}", long);
+    }
+}
+
+// T is whatever the caller's variable holds, through &mut T it must stay exactly that.
+fn feed<T>(input: &mut T, value: T) {
+    *input = value;
+}
+
+fn example_2() {
+    {
+        let mut pet: &'static str = "meow";
+        {
+            let spike = String::from("bark");
+            // if &mut were covariant T could shrink to &'spike str and spike would be
+            // written into pet, which outlives it
+            //feed(&mut pet, spike.as_str()); // this will cause an error
+            println!("{} and {}", pet, spike);
+        }
+        feed(&mut pet, "purr"); // a 'static value is exactly the right type
+        println!("{}", pet);
+    }
+
+    {
+        // a variable with a short lifetime to begin with can be fed a short value
+        let first = String::from("first");
+        let second = String::from("second");
+        let mut current: &str = &first;
+        feed(&mut current, &second);
+        println!("{}", current);
+    }
+}
+
+fn example_3() {
+    // Cell lets us write through a shared reference, so it must be invariant like &mut.
+    {
+        let cell: Cell<&'static str> = Cell::new("static");
+        {
+            let local = String::from("local");
+            //let short: &Cell<&str> = &cell; short.set(&local); // this will cause an error
+            println!("{} {}", cell.get(), local);
+        }
+        cell.set("another static");
+        println!("{}", cell.get());
+    }
+
+    {
+        let outer = String::from("outer");
+        let inner = String::from("inner");
+        let cell = Cell::new(outer.as_str()); // Cell<&'x str> where 'x fits both borrows
+        cell.set(&inner);
+        println!("{}", cell.get());
+    }
+}
+
+fn print_any(s: &str) {
+    println!("any: {}", s);
+}
+
+fn print_static(s: &'static str) {
+    println!("static: {}", s);
+}
+
+fn example_4() {
+    {
+        let exact: fn(&'static str) = print_static;
+        // print_any accepts every &str, so it can do the job of a fn(&'static str)
+        let general: fn(&'static str) = print_any;
+        for handler in [exact, general] {
+            handler("hello");
+        }
+
+        // print_static would be handed borrows it can not accept
+        //let handler: fn(&str) = print_static; // this will cause an error
+        let handler: fn(&str) = print_any;
+        let local = String::from("local");
+        handler(&local);
+    }
+}
+
+// A raw pointer borrows nothing as far as the borrow checker knows. PhantomData<&'a T>
+// makes View act like it holds a &'a T, so it is covariant in 'a and keeps the slice
+// borrowed. PhantomData<Cell<&'a T>> would make it invariant and PhantomData<fn(&'a T)>
+// contravariant.
+struct View<'a, T> {
+    ptr: *const T,
+    len: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> View<'a, T> {
+    fn new(items: &'a [T]) -> View<'a, T> {
+        View { ptr: items.as_ptr(), len: items.len(), _marker: PhantomData }
+    }
+
+    fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.len {
+            // safe because PhantomData keeps the slice borrowed for 'a
+            unsafe { Some(&*self.ptr.add(index)) }
+        } else {
+            None
+        }
+    }
+}
+
+fn example_5() {
+    {
+        let data = vec![10, 20, 30];
+        let view = View::new(&data);
+        //drop(data); // this will cause an error
+        println!("{:?} {:?}", view.get(1), view.get(3));
+        drop(data); // fine, view is not used again
+    }
+}
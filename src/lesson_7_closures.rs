@@ -0,0 +1,207 @@
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
+
+
+/***********************************************************/
+/* Lesson 7: Closures - borrowing the environment */
+/***********************************************************/
+
+/// In lesson 3 apply_to_str was handed |s, _g| &s[0..4], a closure that only used its
+/// arguments. Most closures also use variables from around them, and to do that the
+/// compiler quietly builds a struct holding those variables. Just like the structs in
+/// lesson 2, if the closure only reads a variable it holds a shared reference, if it
+/// changes it the closure holds a mutable reference, and only with move does it own it.
+///
+/// So a closure is a borrow that lasts from where it is written to where it is last
+/// called. While it lives the usual rules apply to everything it captured, and a
+/// closure that must outlive the function that made it has to move its captures in or
+/// carry their lifetime in its type with impl Fn() + 'a.
+
+/********************/
+/*   Vocabulary     */
+/********************/
+
+/// Closure: An anonymous function written |args| body that can use variables from the
+///          scope it is written in.
+/// Capture: A variable from the surrounding scope that a closure uses, held by shared
+///          reference, mutable reference or by value.
+/// Disjoint Capture: Since edition 2021 a closure captures only the fields it uses,
+///                   player.score instead of all of player.
+/// Move Closure: A closure written move |args| body, it takes ownership of every capture.
+/// FnMut: The trait for closures that change what they capture, calling one needs
+///        the closure to be mutable.
+
+////////////////////////////////////////////////////////////////
+/* Lesson 7: Closures */
+////////////////////////////////////////////////////////////////
+
+pub(crate) struct ClosureLesson;
+
+impl Lesson for ClosureLesson {
+    fn id(&self) -> u32 {
+        7
+    }
+    fn title(&self) -> &'static str {
+        "Closures capturing references and move"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Closure",
+            "Capture",
+            "Disjoint Capture",
+            "Move Closure",
+            "FnMut",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "a closure borrows what it uses", run: example_1 },
+            Example { id: "2", title: "capturing only the field that is used", run: example_2 },
+            Example { id: "3", title: "move closures outlive their scope", run: example_3 },
+            Example { id: "4", title: "returning impl Fn() + 'a", run: example_4 },
+            Example { id: "5", title: "FnMut holds its borrow until the last call", run: example_5 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_7_closures.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "2",
+                prompt: "A closure bumps player.score while player.name is printed.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_pass/lesson_7_example_2_read_other_field.rs"),
+                    compiles: true,
+                },
+                explanation: "bump captures &mut player.score, not &mut player",
+            },
+            Question {
+                example: "3",
+                prompt: "Why does make_counter need move when it returns || text.len()?",
+                kind: Kind::Choice {
+                    options: &["text would be borrowed after make_counter returned and dropped it",
+                               "Fn closures may not call methods",
+                               "String does not implement Copy so it can not be read"],
+                    answer: 0,
+                },
+                explanation: "text belongs to make_counter and is dropped when it returns, so a closure that only borrows it could not be handed back",
+            },
+            Question {
+                example: "5",
+                prompt: "count is printed between two calls to a closure that adds to it.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_7_example_5_read_between_calls.rs"),
+                    compiles: false,
+                },
+                explanation: "tally changes count so it is FnMut and holds &mut count until its last call",
+            },
+        ]
+    }
+}
+
+fn example_1() {
+    {
+        let mut name = String::from("Ferris");
+
+        // greet only reads name, so it holds &name
+        let greet = || println!("hello {}", name);
+        greet();
+        println!("we can still read {}", name); // two shared borrows are fine
+        //name.push_str(" the crab"); // this will cause an error
+        greet();
+
+        // greet is not used again, the borrow is over
+        name.push_str(" the crab");
+        println!("{}", name);
+    }
+}
+
+struct Player {
+    name: String,
+    score: u32,
+}
+
+impl Player {
+    fn describe(&self) -> String {
+        format!("{} has {} points", self.name, self.score)
+    }
+}
+
+fn example_2() {
+    {
+        let mut player = Player { name: String::from("ann"), score: 0 };
+
+        // bump captures &mut player.score, not &mut player
+        let mut bump = || player.score += 1;
+        bump();
+        println!("{} is playing", player.name); // a different field, edition 2018 would refuse this
+        //println!("{}", player.describe()); // this will cause an error
+        bump();
+
+        println!("{}", player.describe()); // describe borrows all of player, fine once bump is done
+    }
+}
+
+fn example_3() {
+    // text belongs to make_counter and is dropped when it returns, so a closure
+    // that only borrows it could not be handed back. move puts text inside the closure.
+    fn make_counter() -> impl Fn() -> usize {
+        let text = String::from("counted");
+        //|| text.len() // this will cause an error
+        move || text.len()
+    }
+
+    {
+        let counter = make_counter();
+        println!("{}", counter());
+
+        let words = ["move", "closures"];
+        let has = {
+            let wanted = String::from("move");
+            move |word: &&str| *word == wanted // wanted lives on inside has
+        };
+        println!("{}", words.iter().any(has));
+    }
+}
+
+fn example_4() {
+    // move copies the &str into the closure but it is still a borrow of the caller's
+    // string, so the closure may not outlive 'a and the return type has to say so
+    //fn prefixer(prefix: &str) -> impl Fn(&str) -> String { // this will cause an error
+    fn prefixer<'a>(prefix: &'a str) -> impl Fn(&str) -> String + 'a {
+        move |s| format!("{}{}", prefix, s)
+    }
+
+    {
+        let marker = String::from("> ");
+        let quote = prefixer(&marker);
+        println!("{}", quote("borrowed"));
+        println!("{}", quote("for 'a"));
+        //drop(marker); // quote still borrows marker
+    }
+}
+
+fn example_5() {
+    {
+        let mut count = 0;
+
+        // tally changes count so it is FnMut and holds &mut count
+        let mut tally = || count += 1;
+        tally();
+        //println!("{}", count); // this will cause an error
+        tally();
+        println!("counted {}", count); // tally is done, count is ours again
+
+        // a closure handed to a function keeps its borrow only until that function returns
+        fn twice(mut f: impl FnMut()) {
+            f();
+            f();
+        }
+        let mut log = Vec::new();
+        twice(|| log.push("called"));
+        println!("{:?}", log);
+    }
+}
@@ -0,0 +1,182 @@
+#![allow(clippy::empty_line_after_doc_comments, clippy::useless_vec)]
+
+use std::sync::Arc;
+use std::thread;
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
+
+
+/***********************************************************/
+/* Lesson 8: Threads - where 'static is really required */
+/***********************************************************/
+
+/// Lesson 1 example 5 warned that it is tempting to put 'static on everything. Here is the
+/// place where the standard library really does ask for it. thread::spawn starts a thread
+/// that may keep running after the function that started it has returned, even after
+/// main's locals are gone, so the closure it runs must be 'static: it may own data but it
+/// may not borrow anything from the stack.
+///
+/// There are two ways to share data with a thread anyway. thread::scope promises that
+/// every thread spawned inside it is joined before scope returns, so those threads may
+/// borrow locals just like any other closure. When threads really must be free to
+/// outlive us, Arc gives each of them its own owning handle to the same data.
+
+/********************/
+/*   Vocabulary     */
+/********************/
+
+/// Thread: An independent path of execution, started with thread::spawn and waited for
+///         with join().
+/// 'static Bound: A requirement written T: 'static, the value must not borrow anything
+///                that could go away, owned data and &'static references qualify.
+/// Scoped Thread: A thread spawned inside thread::scope, it is joined before scope returns
+///                so it may borrow from the enclosing function.
+/// Arc: An atomically reference counted pointer, each clone owns a share of the value and
+///      the last one dropped frees it.
+
+////////////////////////////////////////////////////////////////
+/* Lesson 8: Threads */
+////////////////////////////////////////////////////////////////
+
+pub(crate) struct ThreadLesson;
+
+impl Lesson for ThreadLesson {
+    fn id(&self) -> u32 {
+        8
+    }
+    fn title(&self) -> &'static str {
+        "Scoped threads and 'static bounds"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Thread",
+            "'static Bound",
+            "Scoped Thread",
+            "Arc",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "thread::spawn needs a 'static closure", run: example_1 },
+            Example { id: "2", title: "move does not make a reference 'static", run: example_2 },
+            Example { id: "3", title: "thread::scope borrows from the stack", run: example_3 },
+            Example { id: "4", title: "Arc when threads must own the data", run: example_4 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_8_threads.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "1",
+                prompt: "thread::spawn is given a closure that sums a local Vec without move.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_8_example_1_spawn_borrows.rs"),
+                    compiles: false,
+                },
+                explanation: "the thread might still be running after this block ends and scores is dropped",
+            },
+            Question {
+                example: "3",
+                prompt: "Why may threads spawned inside thread::scope borrow locals?",
+                kind: Kind::Choice {
+                    options: &["scope makes every local 'static",
+                               "every thread spawned inside it is joined before scope returns",
+                               "scoped threads copy the data they use"],
+                    answer: 1,
+                },
+                explanation: "every thread spawned in the scope is joined before scope returns, so they may borrow scores and doubled",
+            },
+            Question {
+                example: "3",
+                prompt: "Two threads in the same scope read every score at once.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_pass/lesson_8_example_3_shared_readers.rs"),
+                    compiles: true,
+                },
+                explanation: "any number of threads may share &scores",
+            },
+        ]
+    }
+}
+
+fn example_1() {
+    {
+        let scores = vec![3, 1, 4];
+
+        // the thread might still be running after this block ends and scores is dropped
+        //let handle = thread::spawn(|| scores.iter().sum::<i32>()); // this will cause an error
+
+        // move hands scores to the thread, the closure owns it and is 'static
+        let handle = thread::spawn(move || scores.iter().sum::<i32>());
+        println!("sum from the thread: {}", handle.join().unwrap());
+        //println!("{:?}", scores); // scores now belongs to the thread
+    }
+}
+
+fn example_2() {
+    {
+        let scores = vec![3, 1, 4];
+        let view = &scores;
+
+        // move moves the reference, not what it points to, view is still a borrow of scores
+        //let handle = thread::spawn(move || view.len()); // this will cause an error
+
+        // owned copies are 'static, a &'static str literal is too
+        let count = view.len();
+        let label: &'static str = "scores";
+        let handle = thread::spawn(move || format!("{} {}", count, label));
+        println!("{}", handle.join().unwrap());
+    }
+}
+
+fn example_3() {
+    {
+        let scores = vec![3, 1, 4, 1, 5, 9];
+        let mut doubled = vec![0; scores.len()];
+
+        thread::scope(|s| {
+            // any number of threads may share &scores
+            let total = s.spawn(|| scores.iter().sum::<i32>());
+            let largest = s.spawn(|| scores.iter().max().copied());
+
+            // each writer gets its own half of doubled through chunks_mut
+            for (chunk, out) in scores.chunks(3).zip(doubled.chunks_mut(3)) {
+                s.spawn(move || {
+                    for (score, slot) in chunk.iter().zip(out) {
+                        *slot = score * 2;
+                    }
+                });
+            }
+            //s.spawn(|| doubled.clear()); // this will cause an error
+
+            println!("total {} largest {:?}", total.join().unwrap(), largest.join().unwrap());
+        }); // every scoped thread is joined here
+
+        println!("{:?}", doubled); // the &mut borrows ended with the scope
+    }
+}
+
+fn example_4() {
+    {
+        let scores = Arc::new(vec![3, 1, 4]);
+
+        // an Arc is moved like any other value, clone it first to keep one for ourselves
+        let first = Arc::clone(&scores);
+        let handle = thread::spawn(move || first.len());
+        //println!("{:?}", first); // this will cause an error
+        println!("the thread counted {}", handle.join().unwrap());
+
+        let mut handles = Vec::new();
+        for id in 0..3 {
+            // each thread gets its own Arc, they all point at the same Vec
+            let scores = Arc::clone(&scores);
+            handles.push(thread::spawn(move || scores[id] * 10));
+        }
+
+        let results: Vec<i32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        println!("{:?}", results);
+        println!("{} owner left of {:?}", Arc::strong_count(&scores), scores);
+    }
+}
@@ -0,0 +1,255 @@
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use std::marker::PhantomPinned;
+use std::ops::Range;
+use std::pin::Pin;
+use crate::lesson::{Example, Lesson};
+use crate::quiz::{Kind, Question};
+
+
+/***********************************************************/
+/* Lesson 9: Self-Referential Structs - holding our own ref */
+/***********************************************************/
+
+/// In lesson 3 example 5 hold_and_ref moved a String into HoldingStruct and handed back a
+/// reference to it. The obvious next step is to keep that reference inside HoldingStruct
+/// too, next to the text it points at. That is a self-referential struct, and plain
+/// references can not express it: the struct would be borrowing itself, so it could never
+/// be moved, and moving values is how Rust returns them from functions.
+///
+/// There are three ways out. Store an index or an offset instead of a reference and turn
+/// it back into a &str when asked. Put the struct behind Pin<Box<_>>, which promises it
+/// never moves again, and point at it with a raw pointer. Or do what crates like ouroboros
+/// generate for us, keep the borrowed data in a wrapper that never lets the borrow out
+/// with a lifetime longer than a borrow of the wrapper itself.
+
+/********************/
+/*   Vocabulary     */
+/********************/
+
+/// Self-Referential Struct: A struct with a field that points into another field of the
+///                          same struct.
+/// Raw Pointer: A *const T or *mut T, a pointer the borrow checker does not track. Reading
+///              through one needs unsafe and the programmer's promise that it is valid.
+/// Pin: A wrapper like Pin<Box<T>> that promises the value behind it will not be moved
+///      again, so pointers into it stay valid.
+/// PhantomPinned: A marker field that makes a struct !Unpin, once pinned it can not be
+///                moved out of its Pin.
+
+////////////////////////////////////////////////////////////////
+/* Lesson 9: Self-Referential Structs */
+////////////////////////////////////////////////////////////////
+
+pub(crate) struct SelfReferentialLesson;
+
+impl Lesson for SelfReferentialLesson {
+    fn id(&self) -> u32 {
+        9
+    }
+    fn title(&self) -> &'static str {
+        "Self-referential structs and Pin"
+    }
+    fn vocabulary(&self) -> &'static [&'static str] {
+        &[
+            "Self-Referential Struct",
+            "Raw Pointer",
+            "Pin",
+            "PhantomPinned",
+        ]
+    }
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example { id: "1", title: "a struct that borrows itself can not move", run: example_1 },
+            Example { id: "2", title: "no function can return one", run: example_2 },
+            Example { id: "3", title: "store an index instead of a reference", run: example_3 },
+            Example { id: "4", title: "Pin<Box<_>> and a raw pointer", run: example_4 },
+            Example { id: "5", title: "a safe wrapper in the style of ouroboros", run: example_5 },
+        ]
+    }
+    fn source(&self) -> &'static str {
+        include_str!("lesson_9_self_referential.rs")
+    }
+    fn quiz(&self) -> &'static [Question] {
+        &[
+            Question {
+                example: "1",
+                prompt: "holding.word borrows holding.text, then holding is moved to a new variable.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_9_example_1_move_self_borrow.rs"),
+                    compiles: false,
+                },
+                explanation: "holding is now borrowed by itself for as long as it is used, it can not move",
+            },
+            Question {
+                example: "3",
+                prompt: "Why can IndexedHolding be returned from a function when HoldingStruct can not?",
+                kind: Kind::Choice {
+                    options: &["it holds a Range<usize>, which borrows nothing",
+                               "Range has a 'static lifetime parameter",
+                               "the String inside is cloned on every move"],
+                    answer: 0,
+                },
+                explanation: "holding keeps a Range<usize> instead of a &str, it borrows nothing and moves like any owned value",
+            },
+            Question {
+                example: "5",
+                prompt: "A word is pushed from inside with_words onto a Vec declared before the call.",
+                kind: Kind::Compiles {
+                    snippet: include_str!("../compile_fail/lesson_9_example_5_smuggle_word.rs"),
+                    compiles: false,
+                },
+                explanation: "with_words only lends words for the length of the call, so none of them may be pushed onto found",
+            },
+        ]
+    }
+}
+
+// word is meant to point into text, 'a would have to be the lifetime of the struct itself
+struct HoldingStruct<'a> {
+    text: String,
+    word: &'a str,
+}
+
+fn example_1() {
+    {
+        // built in place it even works, holding.word borrows holding.text
+        let mut holding = HoldingStruct { text: String::from("Hello world"), word: "" };
+        holding.word = holding.text.split(' ').next().unwrap();
+        println!("{} from {}", holding.word, holding.text);
+
+        // but holding is now borrowed by itself for as long as it is used, it can not move
+        //let moved = holding; // this will cause an error
+        //holding.text.push('!'); // and can not be changed either
+    }
+}
+
+fn example_2() {
+    // A constructor has to move text into the struct after borrowing it, and the result
+    // would carry a borrow of a parameter out of the function, like lesson 3 example 5.
+    //we know this is not allowed:
+    //   fn hold<'a>(text: String) -> HoldingStruct<'a> {
+    //       let word = text.split(' ').next().unwrap();
+    //       HoldingStruct { text, word }
+    //   }
+    {
+        // what does work is keeping the owner outside, then the struct only borrows it
+        let text = String::from("Hello world");
+        let holding = HoldingStruct { text: String::new(), word: text.split(' ').next().unwrap() };
+        println!("{} borrowed from {}", holding.word, text);
+    }
+}
+
+// A range into text borrows nothing, so the struct moves freely. The reference is
+// made fresh on each call and borrows self like any other method.
+struct IndexedHolding {
+    text: String,
+    word: Range<usize>,
+}
+
+impl IndexedHolding {
+    fn new(text: String) -> IndexedHolding {
+        let end = text.find(' ').unwrap_or(text.len());
+        IndexedHolding { text, word: 0..end }
+    }
+
+    fn word(&self) -> &str {
+        &self.text[self.word.clone()]
+    }
+
+    fn set_text(&mut self, text: String) {
+        *self = IndexedHolding::new(text); // the index is rebuilt with the text
+    }
+}
+
+fn example_3() {
+    {
+        let holding = IndexedHolding::new(String::from("Hello world"));
+        let mut moved = holding; // no borrow, no problem
+        println!("{}", moved.word());
+        moved.set_text(String::from("Goodbye world"));
+        println!("{}", moved.word());
+    }
+}
+
+// A real pointer to our own field. It is only valid while the struct stays where it
+// was when the pointer was taken, which is exactly what Pin promises. PhantomPinned
+// takes away the Unpin that would let safe code move it out again.
+struct PinnedHolding {
+    text: String,
+    text_ref: *const String,
+    _pin: PhantomPinned,
+}
+
+impl PinnedHolding {
+    fn new(text: String) -> Pin<Box<PinnedHolding>> {
+        let mut boxed = Box::pin(PinnedHolding { text, text_ref: std::ptr::null(), _pin: PhantomPinned });
+        let text_ref: *const String = &boxed.text;
+        // safe because we only write a field, nothing is moved out of the pin
+        unsafe {
+            boxed.as_mut().get_unchecked_mut().text_ref = text_ref;
+        }
+        boxed
+    }
+
+    fn text_ref(self: Pin<&Self>) -> &String {
+        // safe because a pinned PinnedHolding has not moved since new set text_ref
+        unsafe { &*self.text_ref }
+    }
+}
+
+fn example_4() {
+    {
+        let pinned = PinnedHolding::new(String::from("Hello world"));
+        println!("{}", pinned.as_ref().text_ref());
+
+        // moving the Box moves the pointer to the heap, the struct itself stays put
+        let moved = pinned;
+        println!("{} still points at our own text: {}", moved.as_ref().text_ref(),
+                 std::ptr::eq(moved.text_ref, &moved.text));
+        //let unpinned = *moved; // this will cause an error
+    }
+}
+
+// What ouroboros writes for us, by hand. words really borrows text's heap buffer, and
+// we store it as 'static because there is no name for "as long as this struct".
+// That is only sound because:
+//   - text is never changed or handed out mutably, so its buffer never moves or frees
+//   - words is declared first, so it is dropped before text
+//   - words is only ever lent out through with_words, for<'this> means the closure must
+//     work for any 'this, so nothing it is given can escape the call
+struct OwnedWords {
+    words: Vec<&'static str>,
+    text: String,
+}
+
+impl OwnedWords {
+    fn new(text: String) -> OwnedWords {
+        // safe because the buffer lives as long as OwnedWords and is never mutated
+        let borrowed: &'static str = unsafe { &*(text.as_str() as *const str) };
+        OwnedWords { words: borrowed.split_whitespace().collect(), text }
+    }
+
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn with_words<R>(&self, f: impl for<'this> FnOnce(&'this [&'this str]) -> R) -> R {
+        f(&self.words) // &'static str shrinks to &'this str, covariance from lesson 6
+    }
+}
+
+fn example_5() {
+    {
+        let owned = OwnedWords::new(String::from("self referential structs are hard"));
+        let moved = owned; // moving the String does not move its heap buffer
+        let count = moved.with_words(|words| words.len());
+        let longest = moved.with_words(|words| words.iter().map(|w| w.len()).max());
+        println!("{} words in '{}', longest {:?}", count, moved.text(), longest);
+
+        let mut found: Vec<&str> = Vec::new();
+        //moved.with_words(|words| found.push(words[0])); // this will cause an error
+        found.extend(moved.text().split_whitespace().take(2)); // borrowing moved itself is fine
+        let kept = moved.with_words(|words| words[0].to_string()); // and an owned copy may leave
+        println!("{:?} {}", found, kept);
+    }
+}
@@ -2,65 +2,124 @@
 mod lesson_1_reference;
 mod lesson_2_struct;
 mod lesson_3_function;
-
-fn main() {
-    lesson_1_reference::examples();
-    lesson_2_struct::examples();
-    lesson_3_function::examples();
-}
-
-
-#[cfg(test)]
-mod tests {
-    use rand::Rng;
-
-    const ATTENDEE_COUNT:usize = 30;
-
-    #[test]
-    fn test_pick_range_confirm() {
-        let x = 42;
-        let mut rng = rand::thread_rng();
-
-        let (min, max) = (0..1000)
-            .map(|_| rng.gen_range(1..=x))
-            .fold((u32::MAX, u32::MIN), |(min, max), val| {
-                (min.min(val), max.max(val))
-            });
-
-        println!("Min: {}, Max: {}", min, max);
-        assert_eq!(1, min);
-        assert_eq!(x, max);
-    }
-
-    #[test]
-    fn test_pick_random() {
-
-        let mut rng = rand::thread_rng();
-
-        println!("The winners are:");
-        let first_result = rng.gen_range(1..=ATTENDEE_COUNT);
-        println!("First Selected Winner: {}", first_result);
-
-        let second_result;
-        loop {
-            let temp = rng.gen_range(1..=ATTENDEE_COUNT);
-            if temp != first_result {
-                second_result = temp;
-                break;
+mod lesson_4_iterators;
+mod lesson_5_trait_objects;
+mod lesson_6_variance;
+mod lesson_7_closures;
+mod lesson_8_threads;
+mod lesson_9_self_referential;
+mod lesson;
+mod cli;
+mod rustc;
+mod compile_fail;
+mod raffle;
+mod roster;
+mod raffle_stats;
+mod elision;
+mod source;
+mod timeline;
+mod borrowck;
+mod quiz;
+mod challenge;
+mod exercises;
+mod glossary;
+mod capture;
+mod handout;
+mod present;
+mod json;
+
+use std::process::ExitCode;
+use rand::seq::SliceRandom;
+use cli::{Command, Format, Program, RaffleMode};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match cli::parse(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            eprintln!("{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let result = match command {
+        Command::Run { selection, format } => lesson::select(&selection).and_then(|selected| match format {
+            Format::Text => {
+                lesson::run_selected(&selected);
+                Ok(())
             }
+            Format::Json => json::run(&selected),
+        }),
+        Command::List => {
+            lesson::print_list();
+            Ok(())
+        }
+        Command::Verify => compile_fail::verify_all().and(elision::verify_table()),
+        Command::Raffle(options) => match options.mode {
+            RaffleMode::Draw => raffle::run(options.seed, options.roster.as_deref(), options.attendees,
+                                            options.winners, &options.log),
+            RaffleMode::Replay => raffle::replay(&options.log, options.roster.as_deref()),
+            RaffleMode::Stats { draws } => raffle_stats::run(options.seed, options.roster.as_deref(),
+                                                             options.attendees, options.winners, draws),
+        },
+        Command::Expand { signature, lifetime_types } => elision::run(&signature, &lifetime_types),
+        Command::Timeline(selection) => lesson::select(&selection).and_then(|selected| {
+            for (lesson, example) in selected {
+                println!("{}", timeline::render(lesson, example)?);
+            }
+            Ok(())
+        }),
+        Command::Present(selection) => lesson::select(&selection).and_then(|selected| {
+            if selected.is_empty() {
+                return Err("nothing selected to present".to_string());
+            }
+            present::run(&selected, &mut std::io::stdin().lock())
+        }),
+        Command::Quiz(selection) => lesson::select(&selection).and_then(|selected| {
+            let questions = quiz::questions(&selected);
+            if questions.is_empty() {
+                return Err("the selected examples have no questions".to_string());
+            }
+            quiz::run(&questions, &mut std::io::stdin().lock(), &mut std::io::stdout())
+                .map(|_| ())
+                .map_err(|e| format!("quiz stopped: {}", e))
+        }),
+        Command::Challenge { selection, scores } => lesson::select(&selection).and_then(|selected| {
+            let mut challenges = challenge::challenges(&selected);
+            if challenges.is_empty() {
+                return Err("the selected examples have no challenges".to_string());
+            }
+            // shuffled so the working twin does not always follow the broken one
+            challenges.shuffle(&mut rand::thread_rng());
+            challenge::run(&challenges, &scores, &mut std::io::stdin().lock(), &mut std::io::stdout()).map(|_| ())
+        }),
+        Command::Check { lesson, file } => exercises::check(lesson, &file),
+        Command::Glossary(term) => glossary::run(term.as_deref()),
+        Command::Export { selection, dir } => lesson::select(&selection).and_then(|selected| handout::export(&selected, &dir)),
+        Command::Borrowck(program) => match program {
+            Program::File(path) => std::fs::read_to_string(&path)
+                .map_err(|e| format!("unable to read {}: {}", path.display(), e))
+                .and_then(|text| borrowck::run(&text)),
+            Program::Text(text) => borrowck::run(&text),
+            Program::Sample(name) => borrowck::sample(&name).and_then(|text| {
+                println!("{}\n", text);
+                borrowck::run(text)
+            }),
+            Program::Samples => {
+                borrowck::print_samples();
+                Ok(())
+            }
+        },
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
         }
-        println!("Second Selected Winner: {}", second_result);
-
-        assert!(
-            first_result >= 1 && first_result <= ATTENDEE_COUNT,
-            "Returned value is out of range"
-        );
-        assert!(
-            second_result >= 1 && second_result <= ATTENDEE_COUNT,
-            "Returned value is out of range"
-        );
     }
 }
-
-
-
@@ -0,0 +1,111 @@
+use std::io::{self, BufRead, Write};
+use crate::cli::Selector;
+use crate::lesson::{self, Example, Lesson};
+use crate::source;
+
+const KEYS: &str = "[enter] next  [b] back  [r] again  [j 2.3] jump  [q] quit";
+
+/// Clears the terminal and homes the cursor so each example starts on a clean screen.
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// What the presenter typed, as a move through the selected examples.
+#[derive(Debug, PartialEq)]
+enum Step {
+    Show(usize),
+    Quit,
+    /// The input made no sense, stay put and say why
+    Stay(String),
+}
+
+/// Reading whole lines keeps us free of terminal raw mode, a clicker sends enter anyway.
+fn step(input: &str, at: usize, selected: &[(&dyn Lesson, &Example)]) -> Step {
+    let last = selected.len() - 1;
+    match input.trim() {
+        "" | "n" | "next" if at == last => Step::Quit,
+        "" | "n" | "next" => Step::Show(at + 1),
+        "b" | "back" | "p" => Step::Show(at.saturating_sub(1)),
+        "r" | "again" => Step::Show(at),
+        "q" | "quit" => Step::Quit,
+        other => {
+            let target = other.strip_prefix("j ").unwrap_or(other).trim();
+            let found = Selector::parse(target).ok().and_then(|selector| {
+                selected.iter().position(|(l, e)| lesson::matches(&selector, *l, e))
+            });
+            match found {
+                Some(index) => Step::Show(index),
+                None => Step::Stay(format!("'{}' is not one of the selected examples, {}", other, KEYS)),
+            }
+        }
+    }
+}
+
+fn show(lesson: &dyn Lesson, example: &Example, at: usize, count: usize) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", CLEAR)?;
+    writeln!(stdout, "lesson {}: {}    ({} of {})", lesson.id(), lesson.title(), at + 1, count)?;
+    writeln!(stdout, "{}", lesson::banner(lesson, example))?;
+    if let Some(text) = source::example_source(lesson, example) {
+        writeln!(stdout, "{}", text)?;
+    }
+    writeln!(stdout, " --------------- output ---------------")?;
+    stdout.flush()?;
+    // the examples print for themselves
+    (example.run)();
+    Ok(())
+}
+
+/// Shows one example at a time with its source above its output, waiting for the
+/// presenter between examples. Ends after the last one, on q or at end of input.
+pub(crate) fn run<R: BufRead>(selected: &[(&dyn Lesson, &Example)], input: &mut R) -> Result<(), String> {
+    let fail = |e: io::Error| format!("presenter stopped: {}", e);
+    let mut at = 0;
+    show(selected[at].0, selected[at].1, at, selected.len()).map_err(fail)?;
+    loop {
+        print!("\n{} > ", KEYS);
+        io::stdout().flush().map_err(fail)?;
+        let mut line = String::new();
+        if input.read_line(&mut line).map_err(fail)? == 0 {
+            println!();
+            return Ok(());
+        }
+        match step(&line, at, selected) {
+            Step::Show(next) => {
+                at = next;
+                show(selected[at].0, selected[at].1, at, selected.len()).map_err(fail)?;
+            }
+            Step::Quit => return Ok(()),
+            Step::Stay(message) => println!("{}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::all_examples;
+
+    #[test]
+    fn test_step_moves_through_examples() {
+        let all = all_examples();
+        let last = all.len() - 1;
+        assert_eq!(Step::Show(1), step("\n", 0, &all));
+        assert_eq!(Step::Show(1), step("n", 0, &all));
+        assert_eq!(Step::Quit, step("", last, &all));
+        assert_eq!(Step::Show(0), step("b", 0, &all));
+        assert_eq!(Step::Show(3), step("back", 4, &all));
+        assert_eq!(Step::Show(4), step("r", 4, &all));
+        assert_eq!(Step::Quit, step("q", 4, &all));
+    }
+
+    #[test]
+    fn test_step_jumps_by_selector() {
+        let all = all_examples();
+        let index = all.iter().position(|(l, e)| l.id() == 2 && e.id == "3.5").unwrap();
+        assert_eq!(Step::Show(index), step("j 2.3.5", 0, &all));
+        assert_eq!(Step::Show(index), step("2.3.5", 0, &all));
+        let lesson_3 = all.iter().position(|(l, _)| l.id() == 3).unwrap();
+        assert_eq!(Step::Show(lesson_3), step("j 3", 0, &all));
+        assert!(matches!(step("j 9.9", 0, &all), Step::Stay(_)));
+        assert!(matches!(step("dance", 0, &all), Step::Stay(_)));
+    }
+}
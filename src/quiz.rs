@@ -0,0 +1,189 @@
+use std::io::{self, BufRead, Write};
+use crate::lesson::{Example, Lesson};
+
+/// A question a lesson asks about one of its examples.
+pub(crate) struct Question {
+    /// Example id the question is about, so `quiz 2.3.5` only asks about 3.5
+    pub(crate) example: &'static str,
+    pub(crate) prompt: &'static str,
+    pub(crate) kind: Kind,
+    /// Why the answer is right for the example's own code, shown once the
+    /// question is answered
+    pub(crate) explanation: &'static str,
+}
+
+pub(crate) enum Kind {
+    Choice { options: &'static [&'static str], answer: usize },
+    /// A whole program, checked against rustc by the tests
+    Compiles { snippet: &'static str, compiles: bool },
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Score {
+    pub(crate) correct: usize,
+    pub(crate) asked: usize,
+}
+
+/// The questions registered for the selected examples, in presentation order.
+pub(crate) fn questions(selected: &[(&'static dyn Lesson, &'static Example)])
+                        -> Vec<(&'static dyn Lesson, &'static Question)> {
+    selected.iter()
+            .flat_map(|(lesson, example)| lesson.quiz().iter()
+                                                .filter(move |q| q.example == example.id)
+                                                .map(move |q| (*lesson, q)))
+            .collect()
+}
+
+fn letter(index: usize) -> char {
+    (b'a' + index as u8) as char
+}
+
+/// Asks until the answer is y or n, None once the input runs out or on q.
+pub(crate) fn yes_no<R: BufRead, W: Write>(prompt: &str, input: &mut R, output: &mut W) -> io::Result<Option<bool>> {
+    loop {
+        match read_answer(prompt, input, output)?.as_deref() {
+            None => return Ok(None),
+            Some("y" | "yes") => return Ok(Some(true)),
+            Some("n" | "no") => return Ok(Some(false)),
+            Some(_) => {}
+        }
+    }
+}
+
+/// One trimmed lowercase line, None at the end of input or when the learner types q.
+fn read_answer<R: BufRead, W: Write>(prompt: &str, input: &mut R, output: &mut W) -> io::Result<Option<String>> {
+    write!(output, "{}", prompt)?;
+    output.flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        writeln!(output)?;
+        return Ok(None);
+    }
+    let line = line.trim().to_lowercase();
+    Ok(if line == "q" { None } else { Some(line) })
+}
+
+/// Reads answers until one is valid, the index of the option or 1 for compiles.
+fn answer<R: BufRead, W: Write>(kind: &Kind, input: &mut R, output: &mut W) -> io::Result<Option<usize>> {
+    let Kind::Choice { options, .. } = kind else {
+        return Ok(yes_no("does it compile? [y/n]: ", input, output)?.map(usize::from));
+    };
+    let prompt = format!("answer [a-{}]: ", letter(options.len() - 1));
+    loop {
+        let Some(line) = read_answer(&prompt, input, output)? else { return Ok(None) };
+        if let Some(picked) = (0..options.len()).find(|&i| line == letter(i).to_string()) {
+            return Ok(Some(picked));
+        }
+    }
+}
+
+/// Asks each question in turn and scores the answers, stopping early if the input ends.
+pub(crate) fn run<R: BufRead, W: Write>(questions: &[(&dyn Lesson, &Question)],
+                                        input: &mut R, output: &mut W) -> io::Result<Score> {
+    let mut score = Score::default();
+    for (number, (lesson, question)) in questions.iter().enumerate() {
+        writeln!(output, "question {} of {}, lesson {} example {}", number + 1, questions.len(),
+                 lesson.id(), question.example)?;
+        writeln!(output, "{}", question.prompt)?;
+        let expected = match &question.kind {
+            Kind::Choice { options, answer } => {
+                for (i, option) in options.iter().enumerate() {
+                    writeln!(output, "  {}) {}", letter(i), option)?;
+                }
+                *answer
+            }
+            Kind::Compiles { snippet, compiles } => {
+                for line in snippet.lines() {
+                    writeln!(output, "    {}", line)?;
+                }
+                usize::from(*compiles)
+            }
+        };
+        let Some(picked) = answer(&question.kind, input, output)? else { break };
+        score.asked += 1;
+        if picked == expected {
+            score.correct += 1;
+            writeln!(output, "correct")?;
+        } else {
+            match &question.kind {
+                Kind::Choice { options, .. } => writeln!(output, "not quite, the answer is {}) {}",
+                                                         letter(expected), options[expected])?,
+                Kind::Compiles { compiles: true, .. } => writeln!(output, "not quite, it compiles")?,
+                Kind::Compiles { compiles: false, .. } => writeln!(output, "not quite, rustc rejects it")?,
+            }
+        }
+        writeln!(output, "  from lesson {}: {}", lesson.id(), question.explanation)?;
+        writeln!(output)?;
+    }
+    writeln!(output, "score: {} of {}", score.correct, score.asked)?;
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::{all_examples, lessons};
+    use crate::rustc;
+
+    #[test]
+    fn test_questions_are_well_formed() {
+        for lesson in lessons() {
+            for question in lesson.quiz() {
+                let at = format!("{}.{}", lesson.id(), question.example);
+                assert!(lesson.examples().iter().any(|e| e.id == question.example), "{} no such example", at);
+                assert!(!question.explanation.trim().is_empty(), "{} has no explanation", at);
+                if let Kind::Choice { options, answer } = question.kind {
+                    assert!(answer < options.len() && options.len() <= 26, "{} bad answer index", at);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compile_questions_match_rustc() {
+        for lesson in lessons() {
+            for question in lesson.quiz() {
+                if let Kind::Compiles { snippet, compiles } = question.kind {
+                    let compiled = rustc::check(snippet).unwrap();
+                    assert_eq!(compiles, compiled.success, "{}.{} {}\n{}",
+                               lesson.id(), question.example, question.prompt, compiled.stderr);
+                }
+            }
+        }
+    }
+
+    fn reply(kind: &Kind, right: bool) -> String {
+        match *kind {
+            Kind::Choice { answer, .. } if right => letter(answer).to_string(),
+            Kind::Choice { answer, options } => letter((answer + 1) % options.len()).to_string(),
+            Kind::Compiles { compiles, .. } => if compiles == right { "y" } else { "n" }.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_run_scores_answers() {
+        let selected: Vec<_> = all_examples().into_iter().filter(|(l, _)| l.id() == 1).collect();
+        let asked = questions(&selected);
+        // a nonsense answer is asked again, then everything is right but the last
+        let mut answers = String::from("zzz\n");
+        for (i, (_, question)) in asked.iter().enumerate() {
+            answers.push_str(&format!("{}\n", reply(&question.kind, i + 1 < asked.len())));
+        }
+
+        let mut output = Vec::new();
+        let score = run(&asked, &mut answers.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(Score { correct: asked.len() - 1, asked: asked.len() }, score, "{}", output);
+        assert!(output.contains("not quite"), "{}", output);
+        assert!(output.contains("from lesson 1:"), "{}", output);
+    }
+
+    #[test]
+    fn test_run_stops_at_end_of_input() {
+        let asked = questions(&all_examples());
+        let answers = format!("{}\n", reply(&asked[0].1.kind, true));
+        let mut output = Vec::new();
+        let score = run(&asked, &mut answers.as_bytes(), &mut output).unwrap();
+        assert_eq!(Score { correct: 1, asked: 1 }, score);
+    }
+}
@@ -0,0 +1,334 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use rand::{Rng, SeedableRng};
+use rand::seq::index;
+use rand_chacha::ChaCha8Rng;
+use crate::roster::Roster;
+
+/// How many people we usually have at a meetup
+pub(crate) const ATTENDEE_COUNT: usize = 30;
+pub(crate) const WINNER_COUNT: usize = 2;
+pub(crate) const AUDIT_LOG: &str = "raffle_audit.log";
+
+/// Where the seed for a draw came from.
+pub(crate) enum Seed {
+    Number(u64),
+    /// A public string everyone in the room can check, eg the meetup date
+    Phrase(String),
+}
+
+impl Seed {
+    pub(crate) fn value(&self) -> u64 {
+        match self {
+            Seed::Number(seed) => *seed,
+            Seed::Phrase(phrase) => seed_from_phrase(phrase),
+        }
+    }
+}
+
+/// FNV-1a, chosen because unlike the std hasher it is guaranteed not to change between releases.
+fn fnv1a(bytes: impl Iterator<Item = u8>) -> u64 {
+    bytes.fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub(crate) fn seed_from_phrase(phrase: &str) -> u64 {
+    fnv1a(phrase.bytes())
+}
+
+/// Fingerprint of who was in the draw and in which order, since winner 3 only
+/// means something together with the roster it was numbered from.
+pub(crate) fn roster_hash(roster: &Roster) -> u64 {
+    let lines = (1..=roster.len()).filter_map(|number| roster.get(number))
+                                  .map(|a| format!("{},{}\n", a.name, a.email.unwrap_or("")));
+    fnv1a(lines.flat_map(String::into_bytes))
+}
+
+/// Draws distinct winners numbered 1..=attendees, without replacement, in the order drawn.
+pub(crate) fn draw<R: Rng + ?Sized>(rng: &mut R, attendees: usize, winners: usize) -> Result<Vec<usize>, String> {
+    if winners > attendees {
+        return Err(format!("can not draw {} winners from {} attendees", winners, attendees));
+    }
+    Ok(index::sample(rng, attendees, winners).into_iter().map(|i| i + 1).collect())
+}
+
+/// The same seed, attendee count and winner count always produce the same winners.
+/// ChaCha8 rather than StdRng because StdRng may change algorithm in any rand release,
+/// and old audit logs have to keep replaying.
+pub(crate) fn seeded_draw(seed: u64, attendees: usize, winners: usize) -> Result<Vec<usize>, String> {
+    draw(&mut ChaCha8Rng::seed_from_u64(seed), attendees, winners)
+}
+
+/// One line of the audit log, enough to replay the draw bit-for-bit.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) time: u64,
+    pub(crate) seed: u64,
+    pub(crate) attendees: usize,
+    pub(crate) drawn: Vec<usize>,
+    /// roster_hash of the roster file, None when attendees were just numbered
+    pub(crate) roster: Option<u64>,
+    pub(crate) phrase: Option<String>,
+}
+
+impl Entry {
+    pub(crate) fn to_line(&self) -> String {
+        let drawn: Vec<String> = self.drawn.iter().map(|w| w.to_string()).collect();
+        let mut line = format!("time={} seed={} attendees={} drawn={}",
+                               self.time, self.seed, self.attendees, drawn.join(","));
+        if let Some(roster) = self.roster {
+            line.push_str(&format!(" roster={:016x}", roster));
+        }
+        if let Some(phrase) = &self.phrase {
+            line.push_str(" phrase=");
+            line.push_str(phrase); // last so it may contain spaces
+        }
+        line
+    }
+
+    pub(crate) fn parse(line: &str) -> Result<Entry, String> {
+        let (fields, phrase) = match line.split_once(" phrase=") {
+            Some((fields, phrase)) => (fields, Some(phrase.to_string())),
+            None => (line, None),
+        };
+        let optional = |key: &str| {
+            fields.split_whitespace()
+                  .find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))
+        };
+        let field = |key: &str| optional(key).ok_or_else(|| format!("audit line is missing {}: {}", key, line));
+        let number = |key: &str| field(key)?.parse::<u64>().map_err(|e| format!("bad {} in audit line: {}", key, e));
+        let drawn = field("drawn")?;
+        Ok(Entry {
+            time: number("time")?,
+            seed: number("seed")?,
+            attendees: number("attendees")? as usize,
+            drawn: if drawn.is_empty() {
+                Vec::new()
+            } else {
+                drawn.split(',')
+                     .map(|w| w.parse().map_err(|e| format!("bad winner in audit line: {}", e)))
+                     .collect::<Result<_, _>>()?
+            },
+            roster: optional("roster").map(|hash| u64::from_str_radix(hash, 16))
+                                      .transpose()
+                                      .map_err(|e| format!("bad roster in audit line: {}", e))?,
+            phrase,
+        })
+    }
+}
+
+fn append(log: &Path, entry: &Entry) -> Result<(), String> {
+    let mut file = OpenOptions::new().create(true)
+                                     .append(true)
+                                     .open(log)
+                                     .map_err(|e| format!("unable to open {}: {}", log.display(), e))?;
+    writeln!(file, "{}", entry.to_line()).map_err(|e| format!("unable to write {}: {}", log.display(), e))
+}
+
+/// Runs the raffle over a roster file when one is given, otherwise over numbered attendees.
+pub(crate) fn run(seed: Option<Seed>, roster: Option<&Path>, attendees: usize, winners: usize, log: &Path) -> Result<(), String> {
+    match roster {
+        Some(path) => {
+            let text = fs::read_to_string(path).map_err(|e| format!("unable to read {}: {}", path.display(), e))?;
+            let roster = Roster::parse(&text)?;
+            println!("Loaded {} attendees from {}", roster.len(), path.display());
+            draw_and_record(seed, Some(&roster), roster.len(), winners, log)
+        }
+        None => draw_and_record(seed, None, attendees, winners, log),
+    }
+}
+
+fn draw_and_record(seed: Option<Seed>, roster: Option<&Roster>, attendees: usize, winners: usize, log: &Path) -> Result<(), String> {
+    let seed = seed.unwrap_or_else(|| Seed::Number(rand::thread_rng().gen()));
+    // announce the seed before drawing so nobody can claim it was picked after the fact
+    match &seed {
+        Seed::Phrase(phrase) => println!("Seed: {} (from \"{}\")", seed.value(), phrase),
+        Seed::Number(value) => println!("Seed: {}", value),
+    }
+    if let Some(roster) = roster {
+        println!("Roster: {:016x}", roster_hash(roster));
+    }
+    let entry = Entry {
+        time: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        seed: seed.value(),
+        attendees,
+        drawn: seeded_draw(seed.value(), attendees, winners)?,
+        roster: roster.map(roster_hash),
+        phrase: match seed {
+            Seed::Phrase(phrase) => Some(phrase),
+            Seed::Number(_) => None,
+        },
+    };
+    append(log, &entry)?;
+
+    println!("The winners are:");
+    for (place, winner) in entry.drawn.iter().enumerate() {
+        match roster.and_then(|roster| roster.get(*winner)) {
+            Some(attendee) => println!("Winner {}: {} ({})", place + 1, attendee.name, winner),
+            None => println!("Winner {}: {}", place + 1, winner),
+        }
+    }
+    println!("Recorded in {}", log.display());
+    Ok(())
+}
+
+/// The winners the entry's seed draws, or what is wrong with the entry.
+fn redraw(entry: &Entry, expected_roster: Option<u64>) -> Result<Vec<usize>, String> {
+    if let Some(phrase) = &entry.phrase {
+        if seed_from_phrase(phrase) != entry.seed {
+            return Err(format!("seed {} is not the seed of \"{}\" ({})", entry.seed, phrase, seed_from_phrase(phrase)));
+        }
+    }
+    if let (Some(logged), Some(expected)) = (entry.roster, expected_roster) {
+        if logged != expected {
+            return Err(format!("drawn from roster {:016x} but the roster given is {:016x}", logged, expected));
+        }
+    }
+    let drawn = seeded_draw(entry.seed, entry.attendees, entry.drawn.len())?;
+    if drawn != entry.drawn {
+        return Err(format!("logged {:?} but seed {} draws {:?}", entry.drawn, entry.seed, drawn));
+    }
+    Ok(drawn)
+}
+
+/// Re-draws every entry in the audit log and confirms the same winners come out.
+/// A seed logged with its phrase must also be the hash of that phrase, and when the
+/// roster file is given every roster draw must have been made from that same roster.
+pub(crate) fn replay(log: &Path, roster: Option<&Path>) -> Result<(), String> {
+    let text = fs::read_to_string(log).map_err(|e| format!("unable to read {}: {}", log.display(), e))?;
+    let roster_text = match roster {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("unable to read {}: {}", path.display(), e))?,
+        None => String::new(),
+    };
+    let expected_roster = roster.map(|_| Roster::parse(&roster_text)).transpose()?.map(|r| roster_hash(&r));
+
+    let mut disputed = 0;
+    for (number, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        // a line that can not even be read or redrawn is disputed too, the rest still get checked
+        match Entry::parse(line).and_then(|entry| redraw(&entry, expected_roster)) {
+            Ok(drawn) => println!("line {}: ok {:?}", number + 1, drawn),
+            Err(problem) => {
+                disputed += 1;
+                println!("line {}: MISMATCH {}", number + 1, problem);
+            }
+        }
+    }
+    match disputed {
+        0 => Ok(()),
+        n => Err(format!("{} draw(s) in {} could not be reproduced", n, log.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rustc::Scratch;
+
+    #[test]
+    #[allow(clippy::manual_range_contains)]
+    fn test_pick_random() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let winners = draw(&mut rng, ATTENDEE_COUNT, WINNER_COUNT).unwrap();
+            assert_eq!(WINNER_COUNT, winners.len());
+            assert_ne!(winners[0], winners[1], "the same attendee won twice");
+            for winner in winners {
+                assert!(
+                    winner >= 1 && winner <= ATTENDEE_COUNT,
+                    "Returned value is out of range"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_everyone_can_win() {
+        let mut winners = draw(&mut rand::thread_rng(), 5, 5).unwrap();
+        winners.sort();
+        assert_eq!(vec![1, 2, 3, 4, 5], winners);
+    }
+
+    #[test]
+    fn test_too_many_winners() {
+        assert!(draw(&mut rand::thread_rng(), 2, 3).is_err());
+    }
+
+    #[test]
+    fn test_same_seed_same_winners() {
+        let seed = seed_from_phrase("2024-10-17 meetup");
+        assert_eq!(seeded_draw(seed, ATTENDEE_COUNT, 3).unwrap(),
+                   seeded_draw(seed, ATTENDEE_COUNT, 3).unwrap());
+        // published FNV-1a test vector, if this moves old seeds would stop replaying
+        assert_eq!(0xaf63_dc4c_8601_ec8c, seed_from_phrase("a"));
+    }
+
+    #[test]
+    fn test_entry_round_trip() {
+        let entry = Entry { time: 7, seed: 42, attendees: 30, drawn: vec![3, 17], roster: None, phrase: Some("rust meetup".into()) };
+        assert_eq!(entry, Entry::parse(&entry.to_line()).unwrap());
+        let entry = Entry { roster: Some(0x00ab_cdef), phrase: None, ..entry };
+        assert!(entry.to_line().ends_with(" roster=0000000000abcdef"));
+        assert_eq!(entry, Entry::parse(&entry.to_line()).unwrap());
+    }
+
+    #[test]
+    fn test_logged_draws_replay() {
+        let scratch = Scratch::new().unwrap();
+        let log = scratch.path.join(AUDIT_LOG);
+        let roster = scratch.path.join("roster.txt");
+        fs::write(&roster, "Ada\nGrace\nLinus\nAda\n").unwrap();
+        run(Some(Seed::Number(1)), None, ATTENDEE_COUNT, WINNER_COUNT, &log).unwrap();
+        run(Some(Seed::Phrase("october".into())), None, 10, 4, &log).unwrap();
+        run(None, Some(&roster), 0, 3, &log).unwrap();
+        assert!(fs::read_to_string(&log).unwrap().contains("attendees=3 "));
+        assert_eq!(3, fs::read_to_string(&log).unwrap().lines().count());
+        replay(&log, None).unwrap();
+        replay(&log, Some(&roster)).unwrap();
+
+        // same size, different people, so the numbers would name someone else
+        fs::write(&roster, "Ada\nGrace\nBarbara\n").unwrap();
+        assert!(replay(&log, Some(&roster)).is_err());
+
+        fs::write(&log, "time=0 seed=1 attendees=30 drawn=1,2,3,4,5\n").unwrap();
+        assert!(replay(&log, None).is_err());
+    }
+
+    #[test]
+    fn test_replay_checks_the_phrase() {
+        let scratch = Scratch::new().unwrap();
+        let log = scratch.path.join(AUDIT_LOG);
+        run(Some(Seed::Phrase("october".into())), None, 10, 2, &log).unwrap();
+        replay(&log, None).unwrap();
+
+        // a seed picked to favour someone, then passed off as coming from a public phrase
+        let entry = Entry::parse(fs::read_to_string(&log).unwrap().trim()).unwrap();
+        let forged = Entry { seed: 7, drawn: seeded_draw(7, 10, 2).unwrap(), ..entry };
+        fs::write(&log, forged.to_line() + "\n").unwrap();
+        assert!(replay(&log, None).is_err());
+    }
+
+    #[test]
+    fn test_replay_goes_past_a_corrupt_line() {
+        let scratch = Scratch::new().unwrap();
+        let log = scratch.path.join(AUDIT_LOG);
+        // the middle line claims more winners than attendees, so it can not be redrawn at all
+        let entry = |attendees, drawn| Entry { time: 0, seed: 1, attendees, drawn, roster: None, phrase: None };
+        let drawn = seeded_draw(1, 10, 2).unwrap();
+        let lines = [entry(10, drawn.clone()), entry(2, vec![1, 2, 3]), entry(10, vec![drawn[1], drawn[0]]), entry(10, drawn)];
+        let lines: Vec<String> = lines.iter().map(Entry::to_line).collect();
+        fs::write(&log, lines.join("\n")).unwrap();
+        let error = replay(&log, None).unwrap_err();
+        assert!(error.starts_with("2 draw(s)"), "{}", error);
+    }
+
+    #[test]
+    fn test_roster_hash_follows_names_and_order() {
+        let hash = |text: &str| roster_hash(&Roster::parse(text).unwrap());
+        assert_eq!(hash("Ada\nGrace\n"), hash("# october\nAda\n\nGrace\n"));
+        assert_ne!(hash("Ada\nGrace\n"), hash("Grace\nAda\n"));
+        assert_ne!(hash("Ada\nGrace\n"), hash("Ada\nGrace\nLinus\n"));
+    }
+}
@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::Path;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use crate::raffle;
+use crate::roster::Roster;
+
+/// Wilson-Hilferty z score above which we call a draw suspicious, about p < 0.001
+const SUSPICIOUS_Z: f64 = 3.09;
+
+/// Tallies from many simulated raffles drawn from one seeded generator.
+pub(crate) struct Stats {
+    pub(crate) draws: u64,
+    pub(crate) attendees: usize,
+    pub(crate) winners: usize,
+    /// wins per attendee, index 0 is attendee 1
+    pub(crate) frequency: Vec<u64>,
+    /// how often each unordered pair won together, indexed by pair_index
+    pub(crate) pairs: Vec<u64>,
+}
+
+/// Position of the pair (low, high) with low < high in a flattened upper triangle.
+fn pair_index(attendees: usize, low: usize, high: usize) -> usize {
+    low * (2 * attendees - low - 1) / 2 + (high - low - 1)
+}
+
+fn validate(attendees: usize, winners: usize, draws: u64) -> Result<(), String> {
+    // each of these leaves a statistic with nothing to divide by, 0 expected wins or 0 degrees of freedom
+    if attendees < 2 {
+        return Err("need at least two attendees to measure fairness".to_string());
+    }
+    if winners == 0 {
+        return Err("need at least one winner per draw to measure fairness".to_string());
+    }
+    if winners >= attendees {
+        return Err(format!("{} winners from {} attendees means everyone wins every draw, there is nothing to measure",
+                           winners, attendees));
+    }
+    if draws == 0 {
+        return Err("need at least one draw to measure fairness".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn simulate(seed: u64, attendees: usize, winners: usize, draws: u64) -> Result<Stats, String> {
+    validate(attendees, winners, draws)?;
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut stats = Stats {
+        draws,
+        attendees,
+        winners,
+        frequency: vec![0; attendees],
+        pairs: vec![0; attendees * (attendees - 1) / 2],
+    };
+    for _ in 0..draws {
+        let mut drawn = raffle::draw(&mut rng, attendees, winners)?;
+        drawn.sort();
+        for (i, first) in drawn.iter().enumerate() {
+            stats.frequency[first - 1] += 1;
+            for second in &drawn[i + 1..] {
+                stats.pairs[pair_index(attendees, first - 1, second - 1)] += 1;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Pearson's statistic against a uniform expectation over the given buckets.
+pub(crate) fn chi_square(observed: &[u64]) -> f64 {
+    let total: u64 = observed.iter().sum();
+    let expected = total as f64 / observed.len() as f64;
+    observed.iter()
+            .map(|&o| (o as f64 - expected).powi(2) / expected)
+            .sum()
+}
+
+/// Wilson-Hilferty approximation turning a chi-square statistic into a standard normal z.
+pub(crate) fn z_score(chi_square: f64, degrees: usize) -> f64 {
+    let k = degrees as f64;
+    let spread = 2.0 / (9.0 * k);
+    ((chi_square / k).cbrt() - (1.0 - spread)) / spread.sqrt()
+}
+
+impl Stats {
+    pub(crate) fn chi_square(&self) -> f64 {
+        chi_square(&self.frequency)
+    }
+
+    /// Only meaningful when each draw has at least two winners
+    pub(crate) fn pair_chi_square(&self) -> Option<f64> {
+        (self.winners >= 2).then(|| chi_square(&self.pairs))
+    }
+}
+
+pub(crate) fn run(seed: Option<raffle::Seed>, roster: Option<&Path>, attendees: usize, winners: usize, draws: u64) -> Result<(), String> {
+    let text = match roster {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("unable to read {}: {}", path.display(), e))?,
+        None => String::new(),
+    };
+    let roster = roster.map(|_| Roster::parse(&text)).transpose()?;
+    let attendees = roster.as_ref().map_or(attendees, Roster::len);
+
+    validate(attendees, winners, draws)?;
+    let seed = seed.map_or_else(rand::random, |seed| seed.value());
+    println!("Seed: {}", seed);
+    let stats = simulate(seed, attendees, winners, draws)?;
+    println!("{} draws of {} winners from {} attendees", stats.draws, stats.winners, stats.attendees);
+
+    let expected = (stats.draws * stats.winners as u64) as f64 / stats.attendees as f64;
+    println!("{:>8} {:>10} {:>10} {:>10}  name", "attendee", "wins", "expected", "deviation");
+    for (index, wins) in stats.frequency.iter().enumerate() {
+        let name = roster.as_ref().and_then(|r| r.get(index + 1)).map_or("", |a| a.name);
+        println!("{:>8} {:>10} {:>10.1} {:>+9.2}%  {}",
+                 index + 1, wins, expected, (*wins as f64 - expected) / expected * 100.0, name);
+    }
+
+    let mut suspicious = report("attendee", stats.chi_square(), stats.attendees - 1);
+    if let Some(pair_chi) = stats.pair_chi_square() {
+        let min = stats.pairs.iter().min().unwrap_or(&0);
+        let max = stats.pairs.iter().max().unwrap_or(&0);
+        let expected = stats.pairs.iter().sum::<u64>() as f64 / stats.pairs.len() as f64;
+        println!("pairs: {} possible, expected {:.1} each, min {} max {}", stats.pairs.len(), expected, min, max);
+        suspicious |= report("pair", pair_chi, stats.pairs.len() - 1);
+    }
+    if suspicious {
+        println!("the draws do NOT look uniform");
+    } else {
+        println!("the draws look uniform");
+    }
+    Ok(())
+}
+
+fn report(what: &str, chi_square: f64, degrees: usize) -> bool {
+    let z = z_score(chi_square, degrees);
+    println!("{} chi-square {:.2} with {} degrees of freedom (z = {:.2})", what, chi_square, degrees, z);
+    z > SUSPICIOUS_Z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pair_index_covers_triangle() {
+        let n = 5;
+        let mut seen = Vec::new();
+        for low in 0..n {
+            for high in low + 1..n {
+                seen.push(pair_index(n, low, high));
+            }
+        }
+        assert_eq!((0..n * (n - 1) / 2).collect::<Vec<_>>(), seen);
+    }
+
+    #[test]
+    fn test_seeded_draws_are_uniform() {
+        let stats = simulate(42, raffle::ATTENDEE_COUNT, raffle::WINNER_COUNT, 20_000).unwrap();
+        // like the old min/max range check, every attendee must be able to win
+        assert!(stats.frequency.iter().all(|&wins| wins > 0));
+        assert_eq!(20_000 * raffle::WINNER_COUNT as u64, stats.frequency.iter().sum::<u64>());
+
+        // 58.3 is the chi-square critical value for 29 degrees of freedom at p = 0.001
+        assert!(stats.chi_square() < 58.3, "chi-square {}", stats.chi_square());
+        let pair_chi = stats.pair_chi_square().unwrap();
+        assert!(z_score(pair_chi, stats.pairs.len() - 1) < SUSPICIOUS_Z, "pair chi-square {}", pair_chi);
+    }
+
+    #[test]
+    fn test_degenerate_inputs_are_rejected() {
+        assert!(simulate(1, 30, 0, 100).is_err());
+        assert!(simulate(1, 30, 2, 0).is_err());
+        assert!(simulate(1, 2, 2, 100).is_err());
+        assert!(simulate(1, 1, 1, 100).is_err());
+        // the smallest case left still has a degree of freedom to report
+        let stats = simulate(1, 2, 1, 100).unwrap();
+        assert!(stats.pair_chi_square().is_none());
+        assert!(z_score(stats.chi_square(), stats.attendees - 1).is_finite());
+    }
+
+    #[test]
+    fn test_biased_counts_are_caught() {
+        let mut counts = vec![100; 30];
+        counts[0] = 300;
+        assert!(z_score(chi_square(&counts), 29) > SUSPICIOUS_Z);
+        assert_eq!(0.0, chi_square(&[7, 7, 7]));
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Attendee<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) email: Option<&'a str>,
+}
+
+/// Roster is a small lifetime lesson of its own. The file is read once into a
+/// String owned by the caller and every name and email below is a &str slice
+/// of that buffer, nothing is copied. The 'a on Roster<'a> is what makes the
+/// compiler insist the buffer outlives the roster:
+///
+///     let text = fs::read_to_string(path)?;   // owner
+///     let roster = Roster::parse(&text)?;     // borrows text for 'a
+///     drop(text);                             // error[E0505], roster is still used below
+///     println!("{}", roster.len());
+#[derive(Debug)]
+pub(crate) struct Roster<'a> {
+    attendees: Vec<Attendee<'a>>,
+}
+
+impl<'a> Roster<'a> {
+    /// Accepts either a CSV file whose header has a name column (and optionally
+    /// an email column) or plain text with one name per line. Blank lines and
+    /// lines starting with # are ignored. Repeated attendees are dropped,
+    /// matching on email when we have one and on the name otherwise.
+    pub(crate) fn parse(text: &'a str) -> Result<Roster<'a>, String> {
+        let mut lines = text.lines()
+                            .map(str::trim)
+                            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                            .peekable();
+        let header = lines.peek().map(|line| split_csv(line));
+        let columns = header.as_ref().and_then(|fields| {
+            let column = |title: &str| fields.iter().position(|f| f.eq_ignore_ascii_case(title));
+            column("name").map(|name| (name, column("email")))
+        });
+
+        let mut attendees = Vec::new();
+        let mut seen = HashSet::new();
+        let mut add = |attendee: Attendee<'a>| {
+            let key = attendee.email.unwrap_or(attendee.name).to_lowercase();
+            if !attendee.name.is_empty() && seen.insert(key) {
+                attendees.push(attendee);
+            }
+        };
+        match columns {
+            Some((name, email)) => {
+                lines.next(); // the header
+                for (number, line) in lines.enumerate() {
+                    let fields = split_csv(line);
+                    let name = *fields.get(name)
+                                      .ok_or_else(|| format!("roster row {} has no name column: {}", number + 2, line))?;
+                    let email = email.and_then(|column| fields.get(column).copied())
+                                     .filter(|email| !email.is_empty());
+                    add(Attendee { name, email });
+                }
+            }
+            None => lines.for_each(|name| add(Attendee { name, email: None })),
+        }
+        Ok(Roster { attendees })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.attendees.len()
+    }
+
+    /// Winners are numbered from 1 in the order of the file
+    pub(crate) fn get(&self, number: usize) -> Option<&Attendee<'a>> {
+        self.attendees.get(number.checked_sub(1)?)
+    }
+}
+
+/// Splits one CSV line on commas that are not inside double quotes, trimming
+/// spaces and the surrounding quotes. Every field is still a slice of the line.
+fn split_csv(line: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push(unquote(&line[start..index]));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(unquote(&line[start..]));
+    fields
+}
+
+fn unquote(field: &str) -> &str {
+    let field = field.trim();
+    field.strip_prefix('"')
+         .and_then(|f| f.strip_suffix('"'))
+         .unwrap_or(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_roster() {
+        let text = "# october meetup\nAda\n\nGrace\n  ada \nLinus\n";
+        let roster = Roster::parse(text).unwrap();
+        assert_eq!(3, roster.len());
+        assert_eq!("Ada", roster.get(1).unwrap().name);
+        assert_eq!("Linus", roster.get(3).unwrap().name);
+        assert!(roster.get(0).is_none());
+    }
+
+    #[test]
+    fn test_csv_roster_dedups_on_email() {
+        let text = "Email,Name\n\
+                    ada@example.com,Ada Lovelace\n\
+                    \"grace@example.com\",\"Hopper, Grace\"\n\
+                    ADA@example.com,Ada L.\n\
+                    ,Linus\n";
+        let roster = Roster::parse(text).unwrap();
+        assert_eq!(3, roster.len());
+        assert_eq!(Attendee { name: "Hopper, Grace", email: Some("grace@example.com") }, *roster.get(2).unwrap());
+        assert_eq!(Attendee { name: "Linus", email: None }, *roster.get(3).unwrap());
+    }
+
+    #[test]
+    fn test_names_borrow_the_buffer() {
+        let text = String::from("Ada\nGrace\n");
+        let roster = Roster::parse(&text).unwrap();
+        let name = roster.get(2).unwrap().name;
+        // no copy was made, the name points into the original text
+        assert!(text.as_bytes().as_ptr_range().contains(&name.as_ptr()));
+    }
+}
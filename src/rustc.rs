@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The result of handing a snippet to the local compiler.
+pub(crate) struct Compiled {
+    pub(crate) success: bool,
+    pub(crate) stderr: String,
+}
+
+impl Compiled {
+    /// Error codes in the order rustc reported them, eg ["E0505"]
+    pub(crate) fn error_codes(&self) -> Vec<&str> {
+        self.stderr.lines()
+                   .filter_map(|line| line.strip_prefix("error["))
+                   .filter_map(|rest| rest.split_once(']'))
+                   .map(|(code, _)| code)
+                   .collect()
+    }
+}
+
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+/// A scratch directory under the system temp dir which is removed when dropped.
+pub(crate) struct Scratch {
+    pub(crate) path: PathBuf,
+}
+
+impl Scratch {
+    pub(crate) fn new() -> std::io::Result<Scratch> {
+        let path = std::env::temp_dir().join(format!("learn-lifetimes-{}-{}",
+                                                     std::process::id(),
+                                                     NEXT_DIR.fetch_add(1, Ordering::Relaxed)));
+        fs::create_dir_all(&path)?;
+        Ok(Scratch { path })
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Honors $RUSTC the same way cargo does so the snippets see the toolchain the project uses.
+pub(crate) fn rustc() -> Command {
+    Command::new(std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+}
+
+/// Type and borrow checks a single file program without producing a binary.
+pub(crate) fn check(source: &str) -> Result<Compiled, String> {
+    check_as(source, "bin")
+}
+
+/// Like check but for snippets without a main, such as bare signatures in a trait.
+pub(crate) fn check_lib(source: &str) -> Result<Compiled, String> {
+    check_as(source, "lib")
+}
+
+fn check_as(source: &str, crate_type: &str) -> Result<Compiled, String> {
+    let scratch = Scratch::new().map_err(|e| format!("unable to create temp dir: {}", e))?;
+    compile(source, &scratch, &["--crate-type", crate_type, "--emit=metadata", "--out-dir", "."])
+}
+
+/// Builds the source with its #[test] functions into a runner at `test_runner(scratch)`.
+pub(crate) fn build_tests(source: &str, scratch: &Scratch) -> Result<Compiled, String> {
+    compile(source, scratch, &["--test", "-o", "snippet"])
+}
+
+pub(crate) fn test_runner(scratch: &Scratch) -> PathBuf {
+    scratch.path.join(format!("snippet{}", std::env::consts::EXE_SUFFIX))
+}
+
+fn compile(source: &str, scratch: &Scratch, args: &[&str]) -> Result<Compiled, String> {
+    let file = scratch.path.join("snippet.rs");
+    fs::write(&file, source).map_err(|e| format!("unable to write {}: {}", file.display(), e))?;
+    // run from the scratch dir so diagnostics name snippet.rs rather than a temp path
+    let output = rustc().args(["--edition", "2021", "-A", "warnings"])
+                        .args(args)
+                        .arg("snippet.rs")
+                        .current_dir(&scratch.path)
+                        .output()
+                        .map_err(|e| format!("unable to run rustc: {}", e))?;
+    Ok(Compiled {
+        success: output.status.success(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
@@ -0,0 +1,284 @@
+use crate::lesson::{Example, Lesson};
+
+/// Blanks out comments and the contents of string and char literals, keeping every
+/// byte offset and newline where it was, so braces and names can be found by position.
+pub(crate) fn mask(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut masked = bytes.to_vec();
+    let blank = |from: usize, to: usize, masked: &mut Vec<u8>| {
+        for byte in &mut masked[from..to] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(b"//") {
+            let end = rest.iter().position(|&b| b == b'\n').map_or(bytes.len(), |p| i + p);
+            blank(i, end, &mut masked);
+            i = end;
+        } else if rest.starts_with(b"/*") {
+            let end = source[i + 2..].find("*/").map_or(bytes.len(), |p| i + 2 + p + 2);
+            blank(i, end, &mut masked);
+            i = end;
+        } else if rest[0] == b'"' {
+            let mut j = i + 1;
+            while j < bytes.len() && bytes[j] != b'"' {
+                j += if bytes[j] == b'\\' { 2 } else { 1 };
+            }
+            blank(i + 1, j.min(bytes.len()), &mut masked);
+            i = j + 1;
+        } else if rest[0] == b'\'' {
+            // a char literal such as '{' or '\n', anything else is a lifetime
+            let len = match rest.get(1) {
+                Some(b'\\') => rest.iter().skip(2).position(|&b| b == b'\'').map(|p| p + 3),
+                Some(_) => source[i + 1..].chars().next().map(|c| c.len_utf8() + 1)
+                                          .filter(|&n| rest.get(n) == Some(&b'\''))
+                                          .map(|n| n + 1),
+                None => None,
+            };
+            match len {
+                Some(len) => {
+                    blank(i + 1, i + len - 1, &mut masked);
+                    i += len;
+                }
+                None => i += 1,
+            }
+        } else {
+            i += 1;
+        }
+    }
+    String::from_utf8(masked).expect("only ascii bytes were replaced, with spaces")
+}
+
+/// Byte range of the item starting at `start` up to and including its closing brace.
+pub(crate) fn item_end(masked: &str, start: usize) -> Option<usize> {
+    let open = start + masked[start..].find('{')?;
+    let mut depth = 0;
+    for (i, c) in masked[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Examples are registered as `fn example_<id>()` with any dot in the id turned into
+/// an underscore, so 3.5 lives in `fn example_3_5()`.
+pub(crate) fn example_fn_name(example: &Example) -> String {
+    format!("example_{}", example.id.replace('.', "_"))
+}
+
+/// The full text of the example's function, signature and braces included.
+pub(crate) fn example_fn_source(lesson: &dyn Lesson, example: &Example) -> Option<&'static str> {
+    let source = lesson.source();
+    let masked = mask(source);
+    let signature = format!("fn {}()", example_fn_name(example));
+    let start = masked.match_indices(&signature)
+                      .map(|(i, _)| i)
+                      .find(|&i| i == 0 || masked[..i].ends_with('\n'))?;
+    Some(&source[start..item_end(&masked, start)?])
+}
+
+/// A struct, enum, trait, fn or impl written at the top level of a lesson file
+struct Item {
+    /// the type an impl is for, otherwise the item's own name
+    name: String,
+    /// byte range in the lesson, the comments and attributes just above it included
+    range: std::ops::Range<usize>,
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Whole word occurrences only, so Pairs does not match EachPairs
+fn mentions(text: &str, name: &str) -> bool {
+    text.match_indices(name).any(|(i, _)| {
+        !text[..i].ends_with(is_word) && !text[i + name.len()..].starts_with(is_word)
+    })
+}
+
+fn top_level_items(source: &str, masked: &str) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut offset = 0;
+    let mut above = None; // where the comments and attributes over the next item begin
+    for line in source.lines() {
+        let start = offset;
+        offset += line.len() + 1;
+        if line.starts_with("//") && !line.starts_with("///") || line.starts_with("#[") {
+            above.get_or_insert(start);
+            continue;
+        }
+        let code = line.trim_start_matches("pub(crate) ").trim_start_matches("pub ");
+        let name = if let Some(rest) = code.strip_prefix("impl") {
+            // `impl<'a, T> Iterator for Pairs<'a, T>` is about Pairs
+            let header = rest.split('{').next().unwrap_or(rest);
+            let ty = header.rsplit(" for ").next().unwrap_or(header).trim_start();
+            let ty = if header.contains(" for ") { ty } else { ty.strip_prefix('<').map_or(ty, |t| t.split_once('>').map_or(t, |(_, t)| t)) };
+            ty.trim_start().split(|c: char| !is_word(c)).next()
+        } else {
+            ["struct ", "enum ", "trait ", "fn "].iter()
+                .find_map(|keyword| code.strip_prefix(keyword))
+                .and_then(|rest| rest.split(|c: char| !is_word(c)).next())
+        };
+        let from = above.take().unwrap_or(start);
+        let Some(name) = name.filter(|name| !name.is_empty()) else { continue };
+        let end = match masked[start..].find(['{', ';']) {
+            Some(i) if masked.as_bytes()[start + i] == b';' => start + i + 1,
+            _ => match item_end(masked, start) {
+                Some(end) => end,
+                None => continue,
+            },
+        };
+        items.push(Item { name: name.to_string(), range: from..end });
+    }
+    items
+}
+
+/// The example's function with every top level struct, fn and impl it uses, directly
+/// or through another of those items, in the order they appear in the lesson.
+pub(crate) fn example_source(lesson: &dyn Lesson, example: &Example) -> Option<String> {
+    let function = example_fn_source(lesson, example)?;
+    let source = lesson.source();
+    let masked = mask(source);
+    let items: Vec<Item> = top_level_items(source, &masked).into_iter()
+                                                             .filter(|item| !item.name.starts_with("example_"))
+                                                             .collect();
+    let at = |text: &str| text.as_ptr() as usize - source.as_ptr() as usize;
+    let mut used_text = vec![masked[at(function)..at(function) + function.len()].to_string()];
+    let mut used = vec![false; items.len()];
+    loop {
+        let found: Vec<usize> = (0..items.len())
+            .filter(|&i| !used[i] && used_text.iter().any(|text| mentions(text, &items[i].name)))
+            .collect();
+        if found.is_empty() {
+            break;
+        }
+        for i in found {
+            used[i] = true;
+            used_text.push(masked[items[i].range.clone()].to_string());
+        }
+    }
+    let mut parts: Vec<&str> = items.iter()
+                                    .zip(&used)
+                                    .filter(|(_, used)| **used)
+                                    .map(|(item, _)| source[item.range.clone()].trim_end())
+                                    .collect();
+    parts.push(function);
+    Some(parts.join("\n\n"))
+}
+
+/// The doc comment prose that opens a lesson, one string per paragraph, stopping
+/// at the first line that is not a /// comment such as the Vocabulary banner.
+pub(crate) fn lesson_prose(source: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let lines = source.lines()
+                      .map(str::trim)
+                      .skip_while(|line| !line.starts_with("///"))
+                      .take_while(|line| line.starts_with("///"));
+    for line in lines {
+        let text = line.trim_start_matches('/').trim();
+        if text.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join(" "));
+                current.clear();
+            }
+        } else {
+            current.push(text);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join(" "));
+    }
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::lessons;
+    use crate::rustc;
+
+    #[test]
+    fn test_mask_keeps_offsets() {
+        let text = "let c = '{'; // { not a brace\nlet s = \"}\"; fn f<'a>(x: &'a str) {}";
+        let masked = mask(text);
+        assert_eq!(text.len(), masked.len());
+        assert_eq!(2, masked.matches(['{', '}']).count());
+        assert!(masked.contains("<'a>"));
+        assert_eq!(text.find('\n'), masked.find('\n'));
+    }
+
+    #[test]
+    fn test_lesson_prose() {
+        let prose = lesson_prose("use x;\n\n/// First line\n/// continues.\n///\n/// Second.\n/***/\n/// Vocabulary");
+        assert_eq!(vec!["First line continues.".to_string(), "Second.".to_string()], prose);
+        for lesson in lessons() {
+            assert!(!lesson_prose(lesson.source()).is_empty(), "lesson {} has no prose", lesson.id());
+        }
+    }
+
+    #[test]
+    fn test_every_example_has_source() {
+        for lesson in lessons() {
+            for example in lesson.examples() {
+                let function = example_fn_source(*lesson, example)
+                    .unwrap_or_else(|| panic!("no source for {}.{}", lesson.id(), example.id));
+                assert!(function.starts_with(&format!("fn {}()", example_fn_name(example))));
+                assert!(function.ends_with('}'));
+                assert!(example_source(*lesson, example).unwrap().ends_with(function));
+            }
+        }
+    }
+
+    #[test]
+    fn test_example_source_compiles_on_its_own() {
+        // what present, handouts and json show must be the whole example, helper types included
+        for lesson in lessons() {
+            let imports: Vec<&str> = lesson.source().lines().filter(|line| line.starts_with("use std::")).collect();
+            for example in lesson.examples() {
+                let program = format!("{}\n\n{}\n\nfn main() {{\n    {}();\n}}\n", imports.join("\n"),
+                                      example_source(*lesson, example).unwrap(), example_fn_name(example));
+                let compiled = rustc::check(&program).unwrap();
+                assert!(compiled.success, "{}.{} does not compile as shown\n{}", lesson.id(), example.id, compiled.stderr);
+            }
+        }
+    }
+
+    fn source_of(lesson_id: u32, example_id: &str) -> String {
+        let lesson = lessons().iter().find(|l| l.id() == lesson_id).unwrap();
+        example_source(*lesson, lesson.examples().iter().find(|e| e.id == example_id).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_example_source_brings_its_items() {
+        let source = source_of(4, "3");
+        assert!(source.starts_with("// Our own iterator is just a struct holding a reference"), "{}", source);
+        assert!(source.contains("struct Pairs<'a, T> {"), "{}", source);
+        assert!(source.contains("impl<'a, T> Iterator for Pairs<'a, T> {"), "{}", source);
+        assert!(!source.contains("struct EachMut"), "{}", source);
+
+        // print_any and print_static are only reached through example 4
+        let source = source_of(6, "4");
+        assert!(source.contains("fn print_any(s: &str)") && source.contains("fn print_static("), "{}", source);
+        assert!(!source_of(6, "5").contains("fn print_any"));
+        assert!(source_of(6, "5").contains("struct View<'a, T>"));
+        assert!(source_of(9, "5").contains("impl OwnedWords {"));
+
+        // nothing at the top level of lesson 2, its structs are nested in the examples
+        let lesson = lessons().iter().find(|l| l.id() == 2).unwrap();
+        let example = &lesson.examples()[2];
+        assert_eq!(example_fn_source(*lesson, example).unwrap(), example_source(*lesson, example).unwrap());
+    }
+}
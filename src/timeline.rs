@@ -0,0 +1,769 @@
+use crate::lesson::{Example, Lesson};
+use crate::source;
+
+// Draws the lifetimes of an example as columns next to its source. Owned values
+// live until they are dropped or their block closes, borrows only live until their
+// last use (NLL) even though the name stays in scope until the block closes.
+//
+// This reads the example's source text, it does not run the borrow checker, so
+// whether a binding is owned or a borrow is a guess: anything initialized from a
+// `&` expression, or from another borrow, is treated as a borrow. A struct literal
+// remembers what each of its fields borrows, and a call into one of the example's
+// own fns or methods follows the lifetime in its return type back to those fields,
+// so `consume_return_a` only carries the borrow held in `a`.
+
+const SOURCE_WIDTH: usize = 56;
+
+const LEGEND: &str = "  O owned value created    | alive    X dropped or its block closed
+  & borrow created         | live     ^ last use    . still in scope but no longer live (NLL)
+  : declared but not yet assigned";
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+enum Kind {
+    #[default]
+    Owned,
+    Borrow,
+}
+
+#[derive(Debug)]
+struct Binding {
+    name: String,
+    kind: Kind,
+    /// names of the values this borrow points into, owned values unless it borrows a struct binding itself
+    borrows: Vec<String>,
+    /// the struct a literal built, eg SplitStruct, so its methods can be looked up
+    ty: Option<String>,
+    /// what each field of that struct borrows
+    fields: Vec<(String, Vec<String>)>,
+    declared: usize,
+    assigned: Option<usize>,
+    last_use: usize,
+    dropped: Option<usize>,
+    scope_end: Option<usize>,
+}
+
+impl Binding {
+    fn end(&self) -> Option<usize> {
+        self.dropped.or(self.scope_end)
+    }
+
+    fn cell(&self, line: usize) -> char {
+        let Some(assigned) = self.assigned else {
+            return if line >= self.declared && self.end().is_none_or(|end| line < end) { ':' } else { ' ' };
+        };
+        let end = self.end().unwrap_or(usize::MAX);
+        if line < self.declared || line > end {
+            return ' ';
+        }
+        if line < assigned {
+            return ':';
+        }
+        match self.kind {
+            Kind::Owned if line == assigned => 'O',
+            Kind::Owned if line == end => 'X',
+            Kind::Owned => '|',
+            Kind::Borrow if line == assigned => '&',
+            Kind::Borrow if line < self.last_use => '|',
+            Kind::Borrow if line == self.last_use => '^',
+            Kind::Borrow => '.',
+        }
+    }
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Byte offsets of the whole word occurrences of name in text
+fn occurrences<'t>(text: &'t str, name: &'t str) -> impl Iterator<Item = usize> + 't {
+    text.match_indices(name).map(|(i, _)| i).filter(move |&i| {
+        let before = text.as_bytes().get(i.wrapping_sub(1)).copied();
+        let after = text.as_bytes().get(i + name.len()).copied();
+        !before.is_some_and(|b| is_word_byte(b) || b == b'.') && !after.is_some_and(is_word_byte)
+    })
+}
+
+fn mentions(text: &str, name: &str) -> bool {
+    occurrences(text, name).next().is_some()
+}
+
+/// The identifier at the start of text, eg `consume_return_a` from `consume_return_a()`
+fn leading_word(text: &str) -> &str {
+    let end = text.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(text.len());
+    &text[..end]
+}
+
+/// Every lifetime named in text in order, `'_` included
+fn lifetimes(text: &str) -> Vec<String> {
+    text.match_indices('\'')
+        .map(|(i, _)| &text[i..i + 1 + leading_word(&text[i + 1..]).len()])
+        .filter(|lifetime| lifetime.len() > 1)
+        .map(String::from)
+        .collect()
+}
+
+/// Splits on commas that are not nested inside brackets, `->` does not close a `<`
+fn split_top(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut previous = ' ';
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            '>' if previous == '-' => {}
+            ')' | ']' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+    parts.push(text[start..].trim());
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+/// The fn called with the argument at `at` and that argument's position, `f(x)` but not `f(x + 1)`
+fn call_around(text: &str, at: usize) -> Option<(&str, usize)> {
+    let after = text[at..].trim_start_matches(|c: char| c.is_alphanumeric() || c == '_').trim_start();
+    if !after.starts_with([',', ')']) || !text[..at].trim_end().ends_with(['(', ',']) {
+        return None;
+    }
+    let mut depth = 0;
+    let mut position = 0;
+    for (i, c) in text[..at].char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' if depth == 0 => {
+                let before = &text[..i];
+                let name_start = before.rfind(|c: char| !(c.is_alphanumeric() || c == '_')).map_or(0, |p| p + 1);
+                let name = &before[name_start..];
+                return (!name.is_empty()).then_some((name, position));
+            }
+            '(' | '[' | '{' => depth -= 1,
+            ',' if depth == 0 => position += 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A struct declared in the lesson, with the lifetime each field's type uses
+struct StructItem {
+    name: String,
+    lifetimes: Vec<String>,
+    fields: Vec<(String, Option<String>)>,
+}
+
+/// A fn or method declared in the lesson, enough of its signature to tell what its result borrows
+struct FnItem {
+    name: String,
+    /// for a method the type it is implemented on and that type's lifetime arguments,
+    /// eg SplitStruct and ['a, 'b] for `impl <'a,'b> SplitStruct<'a,'b>`
+    owner: Option<(String, Vec<String>)>,
+    params: Vec<String>,
+    returns: Option<String>,
+}
+
+enum Returns {
+    /// nothing borrowed comes back, an owned value, () or only 'static references
+    Nothing,
+    /// a reference whose lifetime the elision rules pick
+    Elided,
+    Lifetime(String),
+}
+
+impl FnItem {
+    fn returns(&self) -> Returns {
+        let Some(returns) = &self.returns else {
+            return Returns::Nothing;
+        };
+        let named = lifetimes(returns);
+        match named.iter().find(|l| *l != "'static") {
+            Some(l) if l == "'_" => Returns::Elided,
+            Some(l) => Returns::Lifetime(l.clone()),
+            None if returns.contains('&') && named.is_empty() => Returns::Elided,
+            None => Returns::Nothing,
+        }
+    }
+
+    fn borrows_self(&self) -> bool {
+        self.params.first().is_some_and(|p| p.contains("self") && p.contains('&'))
+    }
+}
+
+#[derive(Default)]
+struct Items {
+    structs: Vec<StructItem>,
+    fns: Vec<FnItem>,
+}
+
+impl Items {
+    /// Reads the struct, impl and fn headers out of masked source, nested ones included
+    fn parse(masked: &str) -> Items {
+        let mut items = Items::default();
+        let mut impls: Vec<(usize, usize, (String, Vec<String>))> = Vec::new();
+        let mut offset = 0;
+        for line in masked.lines() {
+            let start = offset + line.len() - line.trim_start().len();
+            offset += line.len() + 1;
+            let trimmed = line.trim().trim_start_matches("pub(crate) ").trim_start_matches("pub ");
+            let Some(open) = masked[start..].find(['{', ';']).map(|i| start + i) else { continue };
+            let header = &masked[start..open];
+            if let Some(rest) = trimmed.strip_prefix("struct ") {
+                let Some(end) = masked[open..].starts_with('{').then(|| source::item_end(masked, start)).flatten() else {
+                    continue;
+                };
+                let name = leading_word(rest).to_string();
+                let fields = split_top(&masked[open + 1..end - 1]).into_iter()
+                    .filter_map(|field| field.split_once(':'))
+                    .map(|(field, ty)| (field.trim().trim_start_matches("pub(crate) ").to_string(),
+                                        lifetimes(ty).into_iter().next()))
+                    .collect();
+                items.structs.push(StructItem { name, lifetimes: lifetimes(header.split('{').next().unwrap_or("")), fields });
+            } else if trimmed.starts_with("impl") {
+                // `impl<'a, T> Iterator for Pairs<'a, T>` is about Pairs, `impl<'a> Holder<'a>` about Holder
+                let ty = header.rsplit(" for ").next().unwrap_or(header);
+                let ty = match ty.strip_prefix("impl") {
+                    Some(rest) if rest.trim_start().starts_with('<') => rest.trim_start().split_once('>').map_or(rest, |(_, t)| t),
+                    Some(rest) => rest,
+                    None => ty,
+                }.trim();
+                if let Some(end) = source::item_end(masked, start) {
+                    impls.push((start, end, (leading_word(ty).to_string(), lifetimes(ty))));
+                }
+            } else if let Some(rest) = trimmed.strip_prefix("fn ") {
+                let Some(params_start) = header.find('(') else { continue };
+                let Some(params_end) = header.rfind(')').filter(|&end| end > params_start) else { continue };
+                let returns = header[params_end..].split_once("->")
+                                                   .map(|(_, r)| r.split(" where ").next().unwrap_or(r).trim().to_string());
+                items.fns.push(FnItem {
+                    name: leading_word(rest).to_string(),
+                    owner: impls.iter().rev().find(|(from, to, _)| (*from..*to).contains(&start)).map(|(_, _, owner)| owner.clone()),
+                    params: split_top(&header[params_start + 1..params_end]).into_iter().map(String::from).collect(),
+                    returns,
+                });
+            }
+        }
+        items
+    }
+
+    fn find_struct(&self, name: &str) -> Option<&StructItem> {
+        self.structs.iter().find(|item| item.name == name)
+    }
+
+    fn find_fn(&self, owner: Option<&str>, name: &str) -> Option<&FnItem> {
+        self.fns.iter().find(|item| item.name == name && item.owner.as_ref().map(|(ty, _)| ty.as_str()) == owner)
+    }
+}
+
+/// Names bound by a let pattern such as `mut x`, `(length, _s_ref)` or `SplitStruct {a,b:_}`
+fn pattern_names(pattern: &str) -> Vec<String> {
+    let pattern = match pattern.find('{') {
+        Some(open) => &pattern[open..],
+        None => pattern,
+    };
+    let mut names = Vec::new();
+    let words: Vec<&str> = pattern.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+                                  .filter(|w| !w.is_empty())
+                                  .collect();
+    for word in words {
+        // in `b:_` or `b: x` the field name is not a binding, whatever follows the colon is
+        let word = word.rsplit(':').next().unwrap_or(word);
+        if !word.is_empty() && word != "_" && word != "mut" && word != "ref"
+            && word.starts_with(|c: char| c.is_lowercase() || c == '_') {
+            names.push(word.to_string());
+        }
+    }
+    names
+}
+
+/// The bindings a struct pattern takes from each field, `{a, b: x}` gives (a, a) and (x, b)
+fn pattern_fields(pattern: &str) -> Vec<(String, String)> {
+    let Some(body) = pattern.split_once('{').and_then(|(_, body)| body.split_once('}')).map(|(body, _)| body) else {
+        return Vec::new();
+    };
+    split_top(body).into_iter()
+                   .filter_map(|field| {
+                       let (field, binding) = field.split_once(':').unwrap_or((field, field));
+                       let binding = binding.trim().trim_start_matches("ref ").trim_start_matches("mut ").trim();
+                       (binding != "_").then(|| (binding.to_string(), field.trim().to_string()))
+                   })
+                   .collect()
+}
+
+/// Splits `let mut s: String = init;` into its pattern and initializer
+fn split_let(statement: &str) -> Option<(&str, Option<&str>)> {
+    let rest = statement.trim().strip_prefix("let ")?;
+    let rest = rest.trim_end().trim_end_matches(';');
+    let (pattern, init) = match rest.find('=') {
+        Some(eq) if rest[eq + 1..].starts_with('=') => (rest, None),
+        Some(eq) => (&rest[..eq], Some(rest[eq + 1..].trim())),
+        None => (rest, None),
+    };
+    // drop a type annotation on a simple pattern, `s:String`
+    let pattern = match pattern.find(':') {
+        Some(colon) if !pattern.contains('{') => &pattern[..colon],
+        _ => pattern,
+    };
+    Some((pattern.trim(), init))
+}
+
+/// What an initializer tells us about the binding it is assigned to
+#[derive(Default)]
+struct Value {
+    kind: Kind,
+    borrows: Vec<String>,
+    ty: Option<String>,
+    fields: Vec<(String, Vec<String>)>,
+}
+
+struct Timeline {
+    bindings: Vec<Binding>,
+    /// indexes into bindings for each open block
+    scopes: Vec<Vec<usize>>,
+    items: Items,
+}
+
+impl Timeline {
+    fn visible(&self, name: &str) -> Option<usize> {
+        // innermost block first, and the latest binding within it, so shadowing resolves like rustc
+        self.scopes.iter()
+                   .rev()
+                   .flat_map(|scope| scope.iter().rev())
+                   .copied()
+                   .find(|&i| self.bindings[i].name == name)
+    }
+
+    /// Owned values an initializer borrows from, directly or through another borrow
+    fn classify(&self, init: &str) -> Value {
+        let init = init.trim().trim_end_matches(';').trim_end();
+        if let Some(value) = self.literal(init) {
+            return value;
+        }
+        // a plain move or copy keeps everything we knew about the original
+        if let Some(binding) = self.visible(init).map(|i| &self.bindings[i]) {
+            return Value { kind: binding.kind,
+                           borrows: binding.borrows.clone(),
+                           ty: binding.ty.clone(),
+                           fields: binding.fields.clone() };
+        }
+
+        let mut borrows = Vec::new();
+        let mut kind = Kind::Owned;
+        for index in self.scopes.iter().flatten().copied() {
+            let binding = &self.bindings[index];
+            if !mentions(init, &binding.name) {
+                continue;
+            }
+            let borrowed = init.match_indices(&binding.name).any(|(i, _)| init[..i].trim_end().ends_with('&'))
+                || init.contains(&format!("&mut {}", binding.name));
+            match binding.kind {
+                Kind::Owned if borrowed => {
+                    kind = Kind::Borrow;
+                    borrows.push(binding.name.clone());
+                }
+                Kind::Borrow => {
+                    for at in occurrences(init, &binding.name) {
+                        if let Some(reached) = self.reached(binding, init, at) {
+                            kind = Kind::Borrow;
+                            borrows.extend(reached);
+                        }
+                    }
+                }
+                Kind::Owned => {}
+            }
+        }
+        if init.starts_with('&') {
+            kind = Kind::Borrow;
+        }
+        borrows.sort();
+        borrows.dedup();
+        Value { kind, borrows, ..Value::default() }
+    }
+
+    /// `SplitStruct {a: &a_string, b: &b_string}`, each field classified on its own
+    fn literal(&self, init: &str) -> Option<Value> {
+        let ty = leading_word(init);
+        let body = init[ty.len()..].trim().strip_prefix('{')?.strip_suffix('}')?;
+        if !ty.starts_with(|c: char| c.is_uppercase()) {
+            return None;
+        }
+        let mut value = Value { ty: Some(ty.to_string()), ..Value::default() };
+        for field in split_top(body) {
+            let (name, expr) = field.split_once(':').unwrap_or((field, field)); // `a` is short for `a: a`
+            let field = self.classify(expr);
+            if field.kind == Kind::Borrow {
+                value.kind = Kind::Borrow;
+            }
+            value.borrows.extend(field.borrows.iter().cloned());
+            value.fields.push((name.trim().to_string(), field.borrows));
+        }
+        value.borrows.sort();
+        value.borrows.dedup();
+        Some(value)
+    }
+
+    /// What the occurrence of a borrow at `at` hands on, None when it hands on no borrow at all
+    fn reached(&self, binding: &Binding, init: &str, at: usize) -> Option<Vec<String>> {
+        let all = Some(binding.borrows.clone());
+        let after = &init[at + binding.name.len()..];
+        if let Some(member) = after.strip_prefix('.') {
+            let name = leading_word(member);
+            if member[name.len()..].trim_start().starts_with('(') {
+                return self.through_method(binding, name);
+            }
+            return match binding.fields.iter().find(|(field, _)| field == name) {
+                Some((_, borrows)) => Some(borrows.clone()),
+                None => all,
+            };
+        }
+        match call_around(init, at) {
+            Some((function, position)) => self.through_call(binding, function, position),
+            None => all,
+        }
+    }
+
+    fn through_method(&self, binding: &Binding, method: &str) -> Option<Vec<String>> {
+        let all = Some(binding.borrows.clone());
+        let Some(item) = binding.ty.as_deref().and_then(|ty| self.items.find_fn(Some(ty), method)) else {
+            return all;
+        };
+        match item.returns() {
+            Returns::Nothing => None,
+            // elision gives the result the lifetime of &self, so it borrows the struct itself
+            Returns::Elided if item.borrows_self() => Some(vec![binding.name.clone()]),
+            Returns::Elided => all,
+            Returns::Lifetime(lifetime) => {
+                let (ty, args) = item.owner.as_ref()?;
+                let position = args.iter().position(|arg| *arg == lifetime);
+                position.and_then(|position| self.field_borrows(binding, ty, position)).or(all)
+            }
+        }
+    }
+
+    fn through_call(&self, binding: &Binding, function: &str, position: usize) -> Option<Vec<String>> {
+        let all = Some(binding.borrows.clone());
+        let Some(item) = self.items.find_fn(None, function) else {
+            return all;
+        };
+        let Returns::Lifetime(lifetime) = item.returns() else {
+            return if matches!(item.returns(), Returns::Nothing) { None } else { all };
+        };
+        let Some(param) = item.params.get(position) else {
+            return all;
+        };
+        let ty = param.split_once(':').map_or(param.as_str(), |(_, ty)| ty).trim();
+        let args = lifetimes(ty);
+        let Some(arg) = args.iter().position(|arg| *arg == lifetime) else {
+            return Some(Vec::new()); // the result comes from some other argument
+        };
+        match binding.ty.as_deref() {
+            Some(name) if leading_word(ty) == name => self.field_borrows(binding, name, arg).or(all),
+            _ => all,
+        }
+    }
+
+    /// Borrows held in the fields whose type uses the struct's lifetime parameter at `position`
+    fn field_borrows(&self, binding: &Binding, ty: &str, position: usize) -> Option<Vec<String>> {
+        let item = self.items.find_struct(ty)?;
+        let lifetime = item.lifetimes.get(position)?;
+        if binding.fields.is_empty() {
+            return None;
+        }
+        let mut borrows: Vec<String> = item.fields.iter()
+            .filter(|(_, field_lifetime)| field_lifetime.as_ref() == Some(lifetime))
+            .filter_map(|(field, _)| binding.fields.iter().find(|(name, _)| name == field))
+            .flat_map(|(_, borrows)| borrows.iter().cloned())
+            .collect();
+        borrows.sort();
+        borrows.dedup();
+        Some(borrows)
+    }
+
+    fn statement(&mut self, line: usize, text: &str) {
+        if let Some((pattern, init)) = split_let(text) {
+            let value = init.map(|init| self.classify(init)).unwrap_or_default();
+            let source = init.and_then(|init| self.visible(init.trim()));
+            for name in pattern_names(pattern) {
+                // `let SplitStruct {a, b:_} = data;` only takes what data's field a borrows
+                let field = source.and_then(|i| {
+                    let field = pattern_fields(pattern).into_iter().find(|(binding, _)| *binding == name)?.1;
+                    self.bindings[i].fields.iter().find(|(f, _)| *f == field).map(|(_, borrows)| borrows.clone())
+                });
+                let (kind, borrows, ty, fields) = match field {
+                    Some(borrows) => (if borrows.is_empty() { Kind::Owned } else { Kind::Borrow }, borrows, None, Vec::new()),
+                    None => (value.kind, value.borrows.clone(), value.ty.clone(), value.fields.clone()),
+                };
+                self.bindings.push(Binding {
+                    name,
+                    kind,
+                    borrows,
+                    ty,
+                    fields,
+                    declared: line,
+                    assigned: init.map(|_| line),
+                    last_use: line,
+                    dropped: None,
+                    scope_end: None,
+                });
+                let index = self.bindings.len() - 1;
+                self.scopes.last_mut().expect("always inside the example block").push(index);
+            }
+            if let Some(init) = init {
+                self.uses(line, init);
+            }
+            return;
+        }
+
+        let trimmed = text.trim();
+        if let Some((target, value)) = trimmed.split_once('=')
+                                              .filter(|(t, v)| !v.starts_with('=') && !t.ends_with(['!', '<', '>']))
+        {
+            // a deferred `a_ref = ...;` for a binding declared with `let a_ref;`
+            if let Some(index) = self.visible(target.trim()).filter(|&i| self.bindings[i].assigned.is_none()) {
+                let value_of = self.classify(value);
+                let binding = &mut self.bindings[index];
+                binding.kind = value_of.kind;
+                binding.borrows = value_of.borrows;
+                binding.ty = value_of.ty;
+                binding.fields = value_of.fields;
+                binding.assigned = Some(line);
+                binding.last_use = line;
+                self.uses(line, value);
+                return;
+            }
+            // `data.a = &text;` points one field somewhere new
+            if let Some((name, field)) = target.trim().split_once('.') {
+                let borrows = self.classify(value).borrows;
+                if let Some(index) = self.visible(name) {
+                    let binding = &mut self.bindings[index];
+                    if let Some(slot) = binding.fields.iter_mut().find(|(f, _)| f == field) {
+                        slot.1 = borrows.clone();
+                        // it still borrowed the old target until now, so the summary keeps both
+                        binding.borrows.extend(borrows);
+                        binding.borrows.sort();
+                        binding.borrows.dedup();
+                    }
+                }
+            }
+        }
+        if let Some(name) = trimmed.strip_prefix("drop(").and_then(|rest| rest.split_once(')')).map(|(n, _)| n.trim()) {
+            if let Some(index) = self.visible(name) {
+                if self.bindings[index].kind == Kind::Owned {
+                    self.bindings[index].dropped = Some(line);
+                }
+            }
+        }
+        self.uses(line, text);
+    }
+
+    fn uses(&mut self, line: usize, text: &str) {
+        let names: Vec<String> = self.scopes.iter().flatten().map(|&i| self.bindings[i].name.clone()).collect();
+        for name in names {
+            if mentions(text, &name) {
+                if let Some(index) = self.visible(&name) {
+                    let binding = &mut self.bindings[index];
+                    binding.last_use = binding.last_use.max(line);
+                }
+            }
+        }
+    }
+
+    fn close(&mut self, line: usize) {
+        for index in self.scopes.pop().unwrap_or_default() {
+            self.bindings[index].scope_end.get_or_insert(line);
+        }
+    }
+}
+
+/// One row of the drawing, the file line number and its source text
+struct Row<'s> {
+    number: usize,
+    text: &'s str,
+}
+
+pub(crate) fn render(lesson: &dyn Lesson, example: &Example) -> Result<String, String> {
+    let text = source::example_fn_source(lesson, example)
+        .ok_or_else(|| format!("no source found for lesson {} example {}", lesson.id(), example.id))?;
+    let first_line = lesson.source()[..text.as_ptr() as usize - lesson.source().as_ptr() as usize].lines().count() + 1;
+    let masked = source::mask(text);
+
+    // structs and fns nested in the example come first, then those in the rest of the lesson
+    let mut items = Items::parse(&masked);
+    let lesson_items = Items::parse(&source::mask(lesson.source()));
+    items.structs.extend(lesson_items.structs);
+    items.fns.extend(lesson_items.fns);
+    let mut timeline = Timeline { bindings: Vec::new(), scopes: Vec::new(), items };
+    let mut rows = Vec::new();
+    let mut skip_until = 0;
+    let mut offset = 0;
+    for (index, (line, code)) in text.lines().zip(masked.lines()).enumerate() {
+        let start = offset;
+        offset += line.len() + 1;
+        if start < skip_until {
+            continue;
+        }
+        let trimmed = code.trim();
+        // nested items have their own scopes and are not part of the timeline
+        if index > 0 && ["fn ", "impl", "struct ", "enum ", "trait "].iter().any(|item| trimmed.starts_with(item)) {
+            skip_until = source::item_end(&masked, start).unwrap_or(masked.len());
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with("#[") {
+            continue;
+        }
+        let row = rows.len();
+        rows.push(Row { number: first_line + index, text: line.trim_end() });
+        if index > 0 {
+            timeline.statement(row, code);
+        }
+        for c in code.chars() {
+            match c {
+                '{' => timeline.scopes.push(Vec::new()),
+                '}' => timeline.close(row),
+                _ => {}
+            }
+        }
+    }
+
+    let bindings = &timeline.bindings;
+    let widths: Vec<usize> = bindings.iter().map(|b| b.name.len().max(1)).collect();
+    let mut out = format!("lesson {} example {}: {}\n{}\n\n", lesson.id(), example.id, example.title, LEGEND);
+    out.push_str(&format!("{:>5}  {:<width$}", "line", "source", width = SOURCE_WIDTH));
+    for (binding, width) in bindings.iter().zip(&widths) {
+        out.push_str(&format!(" {:^width$}", binding.name, width = width));
+    }
+    out.push('\n');
+    for (row, line) in rows.iter().enumerate() {
+        let shown: String = line.text.chars().take(SOURCE_WIDTH).collect();
+        let mut text = format!("{:>5}  {:<width$}", line.number, shown, width = SOURCE_WIDTH);
+        for (binding, width) in bindings.iter().zip(&widths) {
+            text.push_str(&format!(" {:^width$}", binding.cell(row), width = width));
+        }
+        out.push_str(text.trim_end());
+        out.push('\n');
+    }
+    out.push('\n');
+    for binding in bindings.iter().filter(|b| b.kind == Kind::Borrow && b.assigned.is_some()) {
+        let from = rows[binding.assigned.unwrap_or(binding.declared)].number;
+        let live_to = rows[binding.last_use].number;
+        let scope_to = binding.scope_end.map_or(live_to, |end| rows[end].number);
+        let target = match binding.borrows.split_last() {
+            None => String::from("a value outside this example"),
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        };
+        out.push_str(&format!("{} borrows {}: live on lines {}-{}, in scope until line {}\n",
+                              binding.name, target, from, live_to, scope_to));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::lessons;
+
+    fn timeline_for(lesson_id: u32, example_id: &str) -> String {
+        let lesson = lessons().iter().find(|l| l.id() == lesson_id).unwrap();
+        let example = lesson.examples().iter().find(|e| e.id == example_id).unwrap();
+        render(*lesson, example).unwrap()
+    }
+
+    #[test]
+    fn test_pattern_names() {
+        assert_eq!(vec!["s"], pattern_names("mut s"));
+        assert_eq!(vec!["length", "_s_ref"], pattern_names("(length, _s_ref)"));
+        assert_eq!(vec!["a"], pattern_names("SplitStruct {a,b:_}"));
+        assert_eq!(Some(("s", Some("String::from(\"x\")"))), split_let("let s:String = String::from(\"x\");"));
+        assert_eq!(Some(("a_ref", None)), split_let("let a_ref;"));
+    }
+
+    #[test]
+    fn test_borrow_ends_at_last_use() {
+        let drawing = timeline_for(1, "1");
+        assert!(drawing.contains("reference1 borrows data"), "{}", drawing);
+        assert!(drawing.contains("reference2 borrows data"), "{}", drawing);
+
+        // in 1.2 reference1 is last used by the first println!, data is read after it
+        let drawing = timeline_for(1, "2");
+        let summary = drawing.lines().find(|l| l.starts_with("reference1 borrows data")).expect(&drawing);
+        let (_, lines) = summary.split_once("live on lines").expect(summary);
+        let numbers: Vec<usize> = lines.split(|c: char| !c.is_ascii_digit())
+                                       .filter_map(|n| n.parse().ok())
+                                       .collect();
+        let &[created, last_use, closed] = numbers.as_slice() else { panic!("{}", summary) };
+        assert!(created < last_use && last_use < closed, "{}", summary);
+        let cell = |line: usize| {
+            let row = drawing.lines().find(|l| l.trim_start().starts_with(&format!("{} ", line))).expect(&drawing);
+            row.trim_end().chars().last().unwrap()
+        };
+        assert_eq!('^', cell(last_use), "{}", drawing);
+        assert_eq!('.', cell(closed), "{}", drawing);
+        assert!(drawing.lines().any(|l| l.contains("println!(\"data: {}\", &data);") && l.ends_with('.')), "{}", drawing);
+    }
+
+    #[test]
+    fn test_reference_outlives_inner_scope() {
+        // in lesson 2 example 2 a_ref is still used after b's block has closed
+        let drawing = timeline_for(2, "2");
+        let a_ref = drawing.lines().find(|l| l.starts_with("a_ref borrows")).expect(&drawing);
+        let s = drawing.lines().find(|l| l.starts_with("s borrows")).expect(&drawing);
+        assert!(a_ref.contains("borrows a:"), "{}", a_ref);
+        assert!(s.contains("a and b"), "{}", s);
+        let last = |line: &str| line.split("until line ").nth(1).unwrap().trim().parse::<usize>().unwrap();
+        assert!(last(a_ref) > last(s));
+    }
+
+    #[test]
+    fn test_struct_borrows_follow_the_returned_lifetime() {
+        let borrows = |drawing: &str, name: &str| -> Vec<String> {
+            drawing.lines()
+                   .filter(|l| l.starts_with(&format!("{} borrows ", name)))
+                   .map(|l| l.split(": live").next().unwrap().to_string())
+                   .collect()
+        };
+        // some_strange_function returns &'a String, the lifetime of field a only
+        let drawing = timeline_for(2, "3");
+        assert_eq!(vec!["a_ref borrows a_string"], borrows(&drawing, "a_ref"), "{}", drawing);
+        assert_eq!(vec!["data borrows a_string and b_string"], borrows(&drawing, "data"));
+
+        let drawing = timeline_for(2, "3.5");
+        assert_eq!(vec!["a_ref borrows a_string", "a_ref borrows a_string"], borrows(&drawing, "a_ref"), "{}", drawing);
+        assert_eq!(vec!["a borrows a_string"], borrows(&drawing, "a"));
+        assert_eq!(vec!["x borrows b_string"], borrows(&drawing, "x"));
+        // process_b(&self) -> &String is elided to the borrow of data itself
+        assert_eq!(vec!["b_ref borrows data"], borrows(&drawing, "b_ref"));
+        assert_eq!(vec!["x_ref borrows data"], borrows(&drawing, "x_ref"));
+        assert_eq!(vec!["data borrows a_string and b_string", "data borrows a_string, b_string and text"],
+                   borrows(&drawing, "data"));
+    }
+
+    #[test]
+    fn test_signatures_are_read_from_the_source() {
+        let items = Items::parse(&source::mask("impl <'a,'b> Split<'a,'b> {\n    fn first(self) -> &'a String {\n    }\n    \
+                                                fn peek(&self) -> &str {\n    }\n}\nfn pick<'a>(d: Split<'a,'b>, n: usize) -> usize {\n}\n"));
+        let first = items.find_fn(Some("Split"), "first").unwrap();
+        assert!(matches!(first.returns(), Returns::Lifetime(l) if l == "'a"));
+        assert_eq!(Some(&("Split".to_string(), vec!["'a".to_string(), "'b".to_string()])), first.owner.as_ref());
+        let peek = items.find_fn(Some("Split"), "peek").unwrap();
+        assert!(matches!(peek.returns(), Returns::Elided) && peek.borrows_self());
+        let pick = items.find_fn(None, "pick").unwrap();
+        assert!(matches!(pick.returns(), Returns::Nothing));
+        assert_eq!(vec!["d: Split<'a,'b>", "n: usize"], pick.params);
+        assert_eq!(Some(("pick", 1)), call_around("pick(d, n)", 8));
+        assert_eq!(None, call_around("pick(d.a, n)", 5));
+    }
+
+    #[test]
+    fn test_every_example_renders() {
+        for lesson in lessons() {
+            for example in lesson.examples() {
+                render(*lesson, example).unwrap();
+            }
+        }
+    }
+}
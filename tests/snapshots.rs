@@ -0,0 +1,67 @@
+//! Golden output for every lesson example.
+//!
+//! Each example is run through the binary and its stdout compared against
+//! tests/snapshots/lesson_<lesson>_example_<example>.txt
+//! After an intended change bless the new output with:
+//!     UPDATE_SNAPSHOTS=1 cargo test --test snapshots
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn learn_lifetimes(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_learn-lifetimes"))
+        .args(args)
+        .output()
+        .expect("unable to run learn-lifetimes");
+    assert!(output.status.success(), "learn-lifetimes {:?} failed: {}",
+            args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).expect("output is not utf8")
+}
+
+/// Example ids such as "2.3.5" taken from `learn-lifetimes list`
+fn example_ids() -> Vec<String> {
+    learn_lifetimes(&["list"]).lines()
+                              .filter(|line| line.starts_with("  "))
+                              .filter_map(|line| line.split_whitespace().next())
+                              .map(String::from)
+                              .collect()
+}
+
+fn snapshot_path(id: &str) -> PathBuf {
+    let (lesson, example) = id.split_once('.').expect("id is lesson.example");
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("lesson_{}_example_{}.txt", lesson, example.replace('.', "_")))
+}
+
+#[test]
+fn test_every_example_matches_its_snapshot() {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let ids = example_ids();
+    assert!(!ids.is_empty(), "no examples were listed");
+
+    let mut mismatched = Vec::new();
+    for id in &ids {
+        let actual = learn_lifetimes(&["run", id]);
+        let path = snapshot_path(id);
+        if update {
+            fs::write(&path, &actual).expect("unable to write snapshot");
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => {
+                eprintln!("--- {} expected\n{}--- {} actual\n{}", path.display(), expected, id, actual);
+                mismatched.push(id.clone());
+            }
+            Err(_) => {
+                eprintln!("--- {} is missing", path.display());
+                mismatched.push(id.clone());
+            }
+        }
+    }
+    assert!(mismatched.is_empty(),
+            "output changed for {:?}, rerun with UPDATE_SNAPSHOTS=1 if this was intended", mismatched);
+}